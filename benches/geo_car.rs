@@ -66,7 +66,7 @@ fn main() {
             .with_samples(1000)
             .run(|| black_box(rrt_connect(
                 black_box(start),
-                black_box(goal),
+                black_box(&goal),
                 KdTreeMap::new(WeightedPoseDistance {
                     position_metric: SquaredEuclidean,
                     position_weight: 1.0,
@@ -124,7 +124,7 @@ fn main() {
     benches.push(Bench::new("geo_maze3d_rrtc").run(|| {
         black_box(rrt_connect(
             black_box(start),
-            black_box(goal),
+            black_box(&goal),
             KdTreeMap::new(SquaredEuclidean),
             black_box(&valid),
             black_box(&sampler),
@@ -148,7 +148,7 @@ fn main() {
         benches.push(Bench::new("geo_maze3d_rrtc_kiddo").run(|| {
             black_box(rrt_connect(
                 black_box(start),
-                black_box(goal),
+                black_box(&goal),
                 rumple::nn::KiddoMap::<_, 3, _, SquaredEuclidean>::new(),
                 black_box(&valid),
                 black_box(&sampler),
@@ -178,7 +178,7 @@ fn main() {
         benches.push(Bench::new("geo_maze3d_rrtc_simd").run(|| {
             black_box(rrt_connect(
                 black_box(start),
-                black_box(goal),
+                black_box(&goal),
                 KdTreeMap::new(SquaredEuclidean),
                 black_box(&rake_valid),
                 black_box(&sampler),
@@ -202,7 +202,7 @@ fn main() {
             benches.push(Bench::new("geo_maze3d_rrtc_simd_kiddo").run(|| {
                 black_box(rrt_connect(
                     black_box(start),
-                    black_box(goal),
+                    black_box(&goal),
                     rumple::nn::KiddoMap::<_, 3, _, SquaredEuclidean>::new(),
                     black_box(&rake_valid),
                     black_box(&sampler),
@@ -0,0 +1,75 @@
+use core::hint::black_box;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rumple::{
+    metric::SquaredEuclidean,
+    nn::{KdTreeMap, NearestNeighborsMap},
+    space::Vector,
+};
+
+use brunch::Bench;
+
+/// The number of points in each tree.
+const N: u32 = 10_000;
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(2707);
+    // points along a narrow corridor, sorted by their x-coordinate, as when growing a tree
+    // through a passage
+    let corridor: Vec<Vector<2>> = (0..N)
+        .map(|i| Vector::new([f64::from(i) * 0.001, rng.gen_range(0.0..0.1)]))
+        .collect();
+    let queries: Vec<Vector<2>> = (0..1_000)
+        .map(|_| Vector::new([rng.gen_range(0.0..10.0), rng.gen_range(0.0..0.1)]))
+        .collect();
+
+    let query_all = |kdt: &KdTreeMap<Vector<2>, (), SquaredEuclidean>| {
+        for q in &queries {
+            black_box(kdt.nearest(black_box(q)));
+        }
+    };
+
+    let mut incremental = KdTreeMap::new(SquaredEuclidean);
+    let mut rebalanced = KdTreeMap::new(SquaredEuclidean).with_rebalancing(true);
+    for &pt in &corridor {
+        incremental.insert(pt, ());
+        rebalanced.insert(pt, ());
+    }
+    let built = KdTreeMap::build(SquaredEuclidean, corridor.iter().map(|&pt| (pt, ())));
+
+    brunch::benches!(
+        inline:
+        Bench::new("kdtree_insert_sorted")
+            .with_samples(100)
+            .run(|| {
+                let mut kdt = KdTreeMap::new(SquaredEuclidean);
+                for &pt in &corridor {
+                    kdt.insert(black_box(pt), ());
+                }
+                kdt
+            }),
+        Bench::new("kdtree_insert_sorted_rebalance")
+            .with_samples(100)
+            .run(|| {
+                let mut kdt = KdTreeMap::new(SquaredEuclidean).with_rebalancing(true);
+                for &pt in &corridor {
+                    kdt.insert(black_box(pt), ());
+                }
+                kdt
+            }),
+        Bench::new("kdtree_build_sorted")
+            .with_samples(100)
+            .run(|| KdTreeMap::build(SquaredEuclidean, corridor.iter().map(|&pt| (black_box(pt), ())))),
+        Bench::spacer(),
+        Bench::new("kdtree_nearest_sorted")
+            .with_samples(100)
+            .run(|| query_all(&incremental)),
+        Bench::new("kdtree_nearest_sorted_rebalance")
+            .with_samples(100)
+            .run(|| query_all(&rebalanced)),
+        Bench::new("kdtree_nearest_sorted_build")
+            .with_samples(100)
+            .run(|| query_all(&built)),
+    )
+}
@@ -56,6 +56,7 @@ fn main() {
         position_dist: 2.0,
     };
 
+    let mut rng = ChaCha20Rng::seed_from_u64(2707);
     let mut rrtc = RrtConnect::new(
         KdTreeMap::new(WeightedPoseDistance {
             position_metric: SquaredEuclidean,
@@ -64,8 +65,9 @@ fn main() {
             angle_weight: 1.0,
         }),
         start,
-        goal,
+        &goal,
         &valid,
+        &mut rng,
     );
 
     let traj = rrtc
@@ -76,7 +78,7 @@ fn main() {
             },
             grow_radius,
             &mut Solved::new(),
-            &mut ChaCha20Rng::seed_from_u64(2707),
+            &mut rng,
         )
         .unwrap();
 
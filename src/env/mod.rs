@@ -1,7 +1,9 @@
 //! Sample environments for testing planners.
+mod svg;
 mod world2d;
 mod world3d;
 
+pub use svg::{Planar, Svg};
 pub use world2d::World2d;
 pub use world3d::World3d;
 
@@ -0,0 +1,271 @@
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+use num_traits::float::FloatCore;
+
+use crate::{
+    geo::{Prm, Tree},
+    space::{Pose2d, Vector},
+};
+
+use super::{Aabb, Ball, World2d};
+
+/// A configuration which can be drawn at a position in the plane.
+pub trait Planar {
+    /// Get the position of this configuration in the plane.
+    fn planar_position(&self) -> [f64; 2];
+}
+
+impl<T: FloatCore> Planar for Vector<2, T> {
+    fn planar_position(&self) -> [f64; 2] {
+        [
+            self[0].to_f64().unwrap_or(f64::NAN),
+            self[1].to_f64().unwrap_or(f64::NAN),
+        ]
+    }
+}
+
+impl<T: FloatCore> Planar for Pose2d<T> {
+    fn planar_position(&self) -> [f64; 2] {
+        self.position.planar_position()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// A writer for SVG drawings of 2-dimensional planning problems.
+///
+/// Worlds, trees, roadmaps, and paths are drawn in world coordinates, with the y-axis pointing
+/// up, in the order they are added.
+/// Colors may be any SVG color, such as `"black"` or `"#ff8000"`.
+///
+/// # Examples
+///
+/// ```
+/// use rumple::{
+///     env::{Svg, World2d},
+///     space::Vector,
+/// };
+///
+/// let mut world = World2d::new();
+/// world.add_aabb(0.4, 0.0, 0.6, 0.6);
+/// world.add_ball(0.2, 0.8, 0.1);
+///
+/// let path = [
+///     Vector::new([0.1, 0.1]),
+///     Vector::new([0.5, 0.8]),
+///     Vector::new([0.9, 0.1]),
+/// ];
+///
+/// let mut svg = Svg::new([0.0, 0.0], [1.0, 1.0]);
+/// svg.world(&world, "gray").path(&path, "red");
+/// let doc = svg.to_string();
+/// assert!(doc.starts_with("<svg"));
+/// ```
+pub struct Svg {
+    /// The lower-left corner of the drawing, in world coordinates.
+    min: [f64; 2],
+    /// The upper-right corner of the drawing, in world coordinates.
+    max: [f64; 2],
+    /// The elements drawn so far.
+    body: String,
+}
+
+impl Svg {
+    /// The width of the drawing in pixels.
+    const WIDTH_PX: f64 = 800.0;
+
+    #[must_use]
+    /// Construct an empty drawing of the rectangle from `min` to `max`, in world coordinates.
+    pub const fn new(min: [f64; 2], max: [f64; 2]) -> Self {
+        Self {
+            min,
+            max,
+            body: String::new(),
+        }
+    }
+
+    /// Get the width of a thin line, in world coordinates.
+    fn stroke_width(&self) -> f64 {
+        (self.max[0] - self.min[0]).max(self.max[1] - self.min[1]) / 500.0
+    }
+
+    /// Draw the obstacles of `world`, filled with `color`.
+    pub fn world<T: FloatCore>(&mut self, world: &World2d<T>, color: &str) -> &mut Self {
+        let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+        for &Aabb {
+            los: [xl, yl],
+            his: [xh, yh],
+        } in &world.aabbs
+        {
+            let _ = writeln!(
+                self.body,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{color}"/>"#,
+                f(xl),
+                f(yl),
+                f(xh - xl),
+                f(yh - yl),
+            );
+        }
+        for &Ball { pos: [x, y], r } in &world.balls {
+            let _ = writeln!(
+                self.body,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{color}"/>"#,
+                f(x),
+                f(y),
+                f(r),
+            );
+        }
+        self
+    }
+
+    /// Draw a line segment along each of `edges` in `color`.
+    fn edges<'c, C: Planar + 'c>(
+        &mut self,
+        edges: impl IntoIterator<Item = (&'c C, &'c C)>,
+        color: &str,
+    ) {
+        let width = self.stroke_width();
+        self.body.push_str(r#"<path d=""#);
+        for (a, b) in edges {
+            let [ax, ay] = a.planar_position();
+            let [bx, by] = b.planar_position();
+            let _ = write!(self.body, "M{ax} {ay}L{bx} {by}");
+        }
+        let _ = writeln!(
+            self.body,
+            r#"" fill="none" stroke="{color}" stroke-width="{width}"/>"#
+        );
+    }
+
+    /// Draw every edge of `tree` in `color`.
+    pub fn tree<C: Planar>(&mut self, tree: &Tree<'_, C>, color: &str) -> &mut Self {
+        let configurations = tree.configurations();
+        self.edges(
+            tree.edges()
+                .map(|(p, c)| (&configurations[p], &configurations[c])),
+            color,
+        );
+        self
+    }
+
+    /// Draw every edge of the roadmap of `prm` in `color`.
+    pub fn roadmap<C: Planar, NN, V>(&mut self, prm: &Prm<'_, C, NN, V>, color: &str) -> &mut Self {
+        self.edges(
+            prm.edges()
+                .filter_map(|(a, b)| prm.configuration(a).zip(prm.configuration(b))),
+            color,
+        );
+        self
+    }
+
+    /// Draw `path` as a thick line in `color`.
+    pub fn path<C: Planar>(&mut self, path: &[C], color: &str) -> &mut Self {
+        let width = 3.0 * self.stroke_width();
+        self.body.push_str(r#"<polyline points=""#);
+        for c in path {
+            let [x, y] = c.planar_position();
+            let _ = write!(self.body, "{x},{y} ");
+        }
+        let _ = writeln!(
+            self.body,
+            r#"" fill="none" stroke="{color}" stroke-width="{width}"/>"#
+        );
+        self
+    }
+
+    /// Draw the footprint of a rectangular robot at each pose in `poses` in `color`.
+    ///
+    /// When its angle is zero, the robot has width `2 * half_w` along the x-axis and height
+    /// `2 * half_h` along the y-axis, matching [`World2d::collides_rect`].
+    pub fn footprints<T: FloatCore>(
+        &mut self,
+        poses: &[Pose2d<T>],
+        half_w: f64,
+        half_h: f64,
+        color: &str,
+    ) -> &mut Self {
+        let width = self.stroke_width();
+        for pose in poses {
+            let [x, y] = pose.planar_position();
+            let degrees = pose.angle.get().to_degrees().to_f64().unwrap_or(f64::NAN);
+            let _ = writeln!(
+                self.body,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" transform="translate({x} {y}) rotate({degrees})" fill="none" stroke="{color}" stroke-width="{width}"/>"#,
+                -half_w,
+                -half_h,
+                2.0 * half_w,
+                2.0 * half_h,
+            );
+        }
+        self
+    }
+
+    #[cfg(feature = "std")]
+    /// Write this drawing to the file at `path`, replacing it if it already exists.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file could not be written.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [xl, yl] = self.min;
+        let [xh, yh] = self.max;
+        let (w, h) = (xh - xl, yh - yl);
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{xl} {} {w} {h}">"#,
+            Self::WIDTH_PX,
+            Self::WIDTH_PX * h / w,
+            -yh,
+        )?;
+        // flip the y-axis so that it points up
+        writeln!(f, r#"<g transform="scale(1 -1)">"#)?;
+        f.write_str(&self.body)?;
+        writeln!(f, "</g>")?;
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::{metric::SquaredEuclidean, nn::KdTreeMap, space::Angle, valid::AlwaysValid};
+
+    #[test]
+    fn draw() {
+        let mut world = World2d::new();
+        world.add_aabb(0.4, 0.0, 0.6, 0.6);
+        world.add_ball(0.2, 0.8, 0.1);
+
+        let mut prm = Prm::new(KdTreeMap::new(SquaredEuclidean), &AlwaysValid);
+        prm.insert_r(Vector::new([0.1, 0.1]), 1.0);
+        prm.insert_r(Vector::new([0.1, 0.2]), 1.0);
+        let poses = [Pose2d {
+            position: Vector::new([0.5, 0.8]),
+            angle: Angle::new(core::f64::consts::FRAC_PI_2),
+        }];
+
+        let doc = Svg::new([0.0, 0.0], [1.0, 2.0])
+            .world(&world, "gray")
+            .roadmap(&prm, "blue")
+            .path(&poses, "red")
+            .footprints(&poses, 0.1, 0.05, "green")
+            .to_string();
+        assert!(doc.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="800" height="1600" viewBox="0 -2 1 2">"#));
+        assert!(doc.contains(
+            r#"<rect x="0.4" y="0" width="0.19999999999999996" height="0.6" fill="gray"/>"#
+        ));
+        assert!(doc.contains(r#"<circle cx="0.2" cy="0.8" r="0.1" fill="gray"/>"#));
+        assert!(doc.contains(r#"<path d="M0.1 0.1L0.1 0.2" fill="none" stroke="blue""#));
+        assert!(doc.contains(r#"<polyline points="0.5,0.8 " fill="none" stroke="red""#));
+        assert!(doc.contains(r#"transform="translate(0.5 0.8) rotate(90)""#));
+        assert!(doc.trim_end().ends_with("</svg>"));
+    }
+}
@@ -4,8 +4,8 @@ use num_traits::float::{FloatConst, FloatCore};
 
 /// A 2-dimensional collision-checking environment.
 pub struct World2d<T = f64> {
-    aabbs: Vec<Aabb<2, T>>,
-    balls: Vec<Ball<2, T>>,
+    pub(super) aabbs: Vec<Aabb<2, T>>,
+    pub(super) balls: Vec<Ball<2, T>>,
 }
 
 impl<T> Default for World2d<T> {
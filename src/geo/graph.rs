@@ -0,0 +1,314 @@
+//! Export of planner graphs to other formats.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use super::Tree;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A graph of configurations built by a planner, such as the roadmap of a
+/// [`Prm`](super::Prm) or the tree of an [`Rrt`](super::Rrt), which can be exported for analysis
+/// in other tools.
+///
+/// Nodes are identified by their index in [`Graph::nodes`].
+/// Configurations are written by a user-supplied formatter or, with the `serde` feature, by their
+/// `serde::Serialize` implementation.
+///
+/// # Examples
+///
+/// ```
+/// use rumple::{
+///     geo::Prm, metric::SquaredEuclidean, nn::KdTreeMap, space::Vector, valid::AlwaysValid,
+/// };
+///
+/// let mut prm = Prm::new(KdTreeMap::new(SquaredEuclidean), &AlwaysValid);
+/// prm.insert_r(Vector::new([0.0, 0.0]), 1.0);
+/// prm.insert_r(Vector::new([0.0, 0.5]), 1.0);
+///
+/// let dot = prm.graph().to_dot(|c| format!("{:?}", c.0));
+/// assert_eq!(
+///     dot,
+///     "graph {\n  0 [label=\"[0.0, 0.0]\"];\n  1 [label=\"[0.0, 0.5]\"];\n  0 -- 1;\n}\n"
+/// );
+/// ```
+pub struct Graph<'g, C> {
+    /// The configuration of each node.
+    nodes: Vec<&'g C>,
+    /// Each edge, as a pair of indices into `nodes`.
+    edges: Vec<(usize, usize)>,
+    /// Whether edges are directed from their first node to their second.
+    directed: bool,
+}
+
+impl<'g, C> Graph<'g, C> {
+    #[must_use]
+    /// Construct a graph with the configurations `nodes`, connected by `edges` between indices into
+    /// `nodes`.
+    /// If `directed` is `true`, each edge is directed from its first node to its second.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if any edge refers to a node which does not exist.
+    pub fn new(nodes: Vec<&'g C>, edges: Vec<(usize, usize)>, directed: bool) -> Self {
+        assert!(
+            edges
+                .iter()
+                .all(|&(a, b)| a < nodes.len() && b < nodes.len()),
+            "edges must connect extant nodes"
+        );
+        Self {
+            nodes,
+            edges,
+            directed,
+        }
+    }
+
+    #[must_use]
+    /// Get the configuration of each node of this graph.
+    pub fn nodes(&self) -> &[&'g C] {
+        &self.nodes
+    }
+
+    #[must_use]
+    /// Get each edge of this graph, as a pair of node indices.
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    #[must_use]
+    /// Return `true` if the edges of this graph are directed.
+    pub const fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    #[must_use]
+    /// Write this graph in the Graphviz DOT language, labeling each node with the string given by
+    /// `format` for its configuration.
+    pub fn to_dot(&self, format: impl Fn(&C) -> String) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let mut out = format!("{kind} {{\n");
+        for (i, c) in self.nodes.iter().enumerate() {
+            let _ = write!(out, "  {i} [label=\"");
+            for ch in format(c).chars() {
+                if matches!(ch, '"' | '\\') {
+                    out.push('\\');
+                }
+                out.push(ch);
+            }
+            out.push_str("\"];\n");
+        }
+        for &(a, b) in &self.edges {
+            let _ = writeln!(out, "  {a} {arrow} {b};");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    #[must_use]
+    /// Write this graph as a `GraphML` document, storing the string given by `format` for each
+    /// node's configuration in its `configuration` attribute.
+    pub fn to_graphml(&self, format: impl Fn(&C) -> String) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"configuration\" for=\"node\" attr.name=\"configuration\" ",
+            "attr.type=\"string\"/>\n",
+        ));
+        let _ = writeln!(
+            out,
+            "  <graph id=\"G\" edgedefault=\"{}\">",
+            if self.directed {
+                "directed"
+            } else {
+                "undirected"
+            }
+        );
+        for (i, c) in self.nodes.iter().enumerate() {
+            let _ = write!(out, "    <node id=\"n{i}\"><data key=\"configuration\">");
+            for ch in format(c).chars() {
+                match ch {
+                    '&' => out.push_str("&amp;"),
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    '"' => out.push_str("&quot;"),
+                    _ => out.push(ch),
+                }
+            }
+            out.push_str("</data></node>\n");
+        }
+        for &(a, b) in &self.edges {
+            let _ = writeln!(out, "    <edge source=\"n{a}\" target=\"n{b}\"/>");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    #[must_use]
+    /// Write this graph as a JSON node-link document, using `format` to write each node's
+    /// configuration as a JSON value.
+    ///
+    /// The document has the same structure as this graph's `serde::Serialize` implementation:
+    /// `{"directed": ..., "multigraph": false, "graph": {}, "nodes": [{"id": 0, "configuration":
+    /// ...}, ...], "edges": [{"source": 0, "target": 1}, ...]}`.
+    /// This can be read by `networkx.node_link_graph(data, edges="edges")`.
+    pub fn to_json(&self, format: impl Fn(&C) -> String) -> String {
+        let mut out = format!(
+            "{{\"directed\":{},\"multigraph\":false,\"graph\":{{}},\"nodes\":[",
+            self.directed
+        );
+        for (i, c) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{{\"id\":{i},\"configuration\":{}}}", format(c));
+        }
+        out.push_str("],\"edges\":[");
+        for (i, &(a, b)) in self.edges.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{{\"source\":{a},\"target\":{b}}}");
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+impl<'g, C> From<Tree<'g, C>> for Graph<'g, C> {
+    /// Construct a directed graph with an edge from each node of `tree` to each of its children.
+    fn from(tree: Tree<'g, C>) -> Self {
+        Self::new(
+            tree.configurations().iter().collect(),
+            tree.edges().collect(),
+            true,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+mod ser {
+    use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+    use super::Graph;
+
+    /// A node of a graph, serialized as `{"id": ..., "configuration": ...}`.
+    struct Node<'r, C>(usize, &'r C);
+
+    /// A sequence of serializable items, produced by a function.
+    struct Seq<F>(usize, F);
+
+    impl<C: Serialize> Serialize for Node<'_, C> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(2))?;
+            map.serialize_entry("id", &self.0)?;
+            map.serialize_entry("configuration", self.1)?;
+            map.end()
+        }
+    }
+
+    impl<T: Serialize, F: Fn(usize) -> T> Serialize for Seq<F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.0))?;
+            for i in 0..self.0 {
+                seq.serialize_element(&(self.1)(i))?;
+            }
+            seq.end()
+        }
+    }
+
+    /// A graph is serialized as a node-link document, in the same structure as
+    /// [`Graph::to_json`].
+    impl<C: Serialize> Serialize for Graph<'_, C> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            /// An edge of a graph, serialized as `{"source": ..., "target": ...}`.
+            #[derive(serde::Serialize)]
+            struct Edge {
+                source: usize,
+                target: usize,
+            }
+
+            /// The attributes of a graph, of which there are none.
+            #[derive(serde::Serialize)]
+            struct Attributes {}
+
+            let mut map = serializer.serialize_map(Some(5))?;
+            map.serialize_entry("directed", &self.directed)?;
+            map.serialize_entry("multigraph", &false)?;
+            map.serialize_entry("graph", &Attributes {})?;
+            map.serialize_entry(
+                "nodes",
+                &Seq(self.nodes.len(), |i: usize| Node(i, self.nodes[i])),
+            )?;
+            map.serialize_entry(
+                "edges",
+                &Seq(self.edges.len(), |i: usize| Edge {
+                    source: self.edges[i].0,
+                    target: self.edges[i].1,
+                }),
+            )?;
+            map.end()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::Vector;
+
+    fn tree_graph<'g>(
+        configurations: &'g [Vector<2>],
+        parents: &'g [usize],
+    ) -> Graph<'g, Vector<2>> {
+        Graph::from(Tree::new(configurations, parents))
+    }
+
+    #[test]
+    fn formats() {
+        let configurations = [
+            Vector::new([0.0, 0.0]),
+            Vector::new([1.0, 0.0]),
+            Vector::new([1.0, 1.0]),
+        ];
+        let graph = tree_graph(&configurations, &[usize::MAX, 0, 0]);
+        assert!(graph.is_directed());
+        assert_eq!(graph.edges(), [(0, 1), (0, 2)]);
+
+        let dot = graph.to_dot(|c| format!("\"{}\"", c[0]));
+        assert_eq!(
+            dot,
+            "digraph {\n  0 [label=\"\\\"0\\\"\"];\n  1 [label=\"\\\"1\\\"\"];\n  \
+             2 [label=\"\\\"1\\\"\"];\n  0 -> 1;\n  0 -> 2;\n}\n"
+        );
+
+        let graphml = graph.to_graphml(|c| format!("<{}>", c[1]));
+        assert!(graphml.contains("<graph id=\"G\" edgedefault=\"directed\">"));
+        assert!(
+            graphml.contains("<node id=\"n2\"><data key=\"configuration\">&lt;1&gt;</data></node>")
+        );
+        assert!(graphml.contains("<edge source=\"n0\" target=\"n2\"/>"));
+
+        let json = graph.to_json(|c| format!("[{},{}]", c[0], c[1]));
+        assert_eq!(
+            json,
+            "{\"directed\":true,\"multigraph\":false,\"graph\":{},\"nodes\":[\
+             {\"id\":0,\"configuration\":[0,0]},{\"id\":1,\"configuration\":[1,0]},\
+             {\"id\":2,\"configuration\":[1,1]}],\"edges\":[\
+             {\"source\":0,\"target\":1},{\"source\":0,\"target\":2}]}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_matches_json() {
+        let configurations = [Vector::new([0.5, 0.0]), Vector::new([1.0, 0.25])];
+        let graph = tree_graph(&configurations, &[usize::MAX, 0]);
+        let formatted: serde_json::Value =
+            serde_json::from_str(&graph.to_json(|c| serde_json::to_string(c).unwrap())).unwrap();
+        assert_eq!(serde_json::to_value(&graph).unwrap(), formatted);
+    }
+}
@@ -5,10 +5,20 @@
 //! interpolation between any state. This module contains implementations of common geometric
 //! planning algorithms as well as some useful primitives for working with geometric states.
 
+mod graph;
+mod neighborhood;
 mod prm;
+mod problem;
 mod rrt;
 mod rrtc;
+mod rrtstar;
+mod tree;
 
+pub use graph::Graph;
+pub use neighborhood::{Neighborhood, Neighbors, RadiusNeighborhood};
 pub use prm::{Prm, PrmNodeId};
+pub use problem::{PlanResult, PlanStats, PlanStatus, Planner, Problem};
 pub use rrt::{rrt, Rrt};
 pub use rrtc::{rrt_connect, RrtConnect};
+pub use rrtstar::{informed_rrt_star, rrt_star, RrtStar};
+pub use tree::Tree;
@@ -0,0 +1,259 @@
+use alloc::vec::Vec;
+use num_traits::Float;
+
+use crate::nn::{KNearestNeighborsMap, NearestNeighborsMap, RangeNearestNeighborsMap};
+
+/// A strategy for choosing the neighbors of a new node among the existing nodes of a graph, which
+/// are stored in the nearest-neighbor map `NN`.
+///
+/// [`Neighborhood`] can choose neighbors from any map supporting both range and _k_-nearest
+/// queries, while [`RadiusNeighborhood`] only requires range queries.
+pub trait Neighbors<K, V, NN>
+where
+    NN: NearestNeighborsMap<K, V>,
+{
+    /// Find the neighbors of `key` in `nn`, assuming that the graph contains `n` nodes.
+    ///
+    /// Each neighbor is returned with its distance to `key`, ordered from nearest to farthest.
+    fn neighbors<'q>(&self, nn: &'q NN, key: &'q K, n: usize) -> Vec<(&'q K, &'q V, NN::Distance)>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A strategy for choosing which existing nodes of a graph a new node should try to connect to.
+///
+/// `R` is the distance type of the nearest-neighbor structure used to find neighbors.
+/// Note that this is measured in the units of the nearest-neighbor structure's metric: if it uses
+/// [`crate::metric::SquaredEuclidean`], all radii are squared distances.
+pub enum Neighborhood<R> {
+    /// Connect to all nodes within a fixed radius.
+    Radius(R),
+    /// Connect to all nodes within a radius which shrinks as the graph grows, as in RRT* and PRM*.
+    ///
+    /// When the graph has `n` nodes, the radius is `gamma * (ln(n) / n)^(1 / dimension)`.
+    /// For asymptotic optimality, `gamma` must be sufficiently large for the configuration space;
+    /// refer to Karaman and Frazzoli (2011) for details.
+    ///
+    /// If the nearest-neighbor metric is squared, `gamma` should also be squared and `dimension`
+    /// should be halved.
+    ShrinkingRadius {
+        /// The scaling constant for the radius.
+        gamma: R,
+        /// The dimension of the configuration space.
+        dimension: R,
+    },
+    /// Connect to a fixed number of nearest nodes, as in k-PRM.
+    KNearest(usize),
+    /// Connect to a number of nearest nodes which grows logarithmically with the size of the
+    /// graph, as in k-PRM* and k-RRT*.
+    ///
+    /// When the graph has `n` nodes, each node connects to `ceil(e * (1 + 1 / dimension) * ln(n))`
+    /// of its nearest neighbors, which is always sufficient for asymptotic optimality.
+    KNearestStar {
+        /// The dimension of the configuration space.
+        dimension: usize,
+    },
+}
+
+impl<R> Neighborhood<R> {
+    /// Compute the radius of the neighborhood in a graph containing `n` nodes.
+    ///
+    /// Returns `None` if this neighborhood is not bounded by a radius.
+    pub fn radius(&self, n: usize) -> Option<R>
+    where
+        R: Float,
+    {
+        match *self {
+            Self::Radius(r) => Some(RadiusNeighborhood::Radius(r).radius(n)),
+            Self::ShrinkingRadius { gamma, dimension } => {
+                Some(RadiusNeighborhood::ShrinkingRadius { gamma, dimension }.radius(n))
+            }
+            Self::KNearest(_) | Self::KNearestStar { .. } => None,
+        }
+    }
+
+    /// Compute the number of nearest neighbors in the neighborhood in a graph containing `n`
+    /// nodes.
+    ///
+    /// Returns `None` if this neighborhood is not bounded by a number of neighbors.
+    pub fn k(&self, n: usize) -> Option<usize>
+    where
+        R: Float,
+    {
+        match *self {
+            Self::Radius(_) | Self::ShrinkingRadius { .. } => None,
+            Self::KNearest(k) => Some(k),
+            Self::KNearestStar { dimension } => {
+                let (Some(n_r), Some(d)) = (R::from(n), R::from(dimension)) else {
+                    return Some(n);
+                };
+                let k = (R::one().exp() * (R::one() + d.recip()) * n_r.ln()).ceil();
+                Some(k.to_usize().unwrap_or(0).min(n))
+            }
+        }
+    }
+}
+
+impl<K, V, NN, R> Neighbors<K, V, NN> for Neighborhood<R>
+where
+    NN: RangeNearestNeighborsMap<K, V, Distance = R> + KNearestNeighborsMap<K, V>,
+    R: Float,
+{
+    fn neighbors<'q>(&self, nn: &'q NN, key: &'q K, n: usize) -> Vec<(&'q K, &'q V, R)> {
+        match (self.radius(n), self.k(n)) {
+            (Some(r), _) => nn.nearest_within_r_sorted(key, r),
+            (None, Some(k)) => nn.k_nearest(key, k),
+            (None, None) => unreachable!("every neighborhood is bounded by a radius or a count"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A strategy for choosing neighbors which is bounded only by a radius.
+///
+/// Unlike [`Neighborhood`], this only requires that the nearest-neighbor structure support range
+/// queries.
+/// As with [`Neighborhood`], radii are measured in the units of the nearest-neighbor structure's
+/// metric.
+pub enum RadiusNeighborhood<R> {
+    /// Connect to all nodes within a fixed radius.
+    Radius(R),
+    /// Connect to all nodes within a radius which shrinks as the graph grows, as in RRT* and PRM*.
+    ///
+    /// See [`Neighborhood::ShrinkingRadius`] for details.
+    ShrinkingRadius {
+        /// The scaling constant for the radius.
+        gamma: R,
+        /// The dimension of the configuration space.
+        dimension: R,
+    },
+}
+
+impl<R> RadiusNeighborhood<R> {
+    /// Compute the radius of the neighborhood in a graph containing `n` nodes.
+    pub fn radius(&self, n: usize) -> R
+    where
+        R: Float,
+    {
+        match *self {
+            Self::Radius(r) => r,
+            Self::ShrinkingRadius { gamma, dimension } => {
+                let Some(n) = R::from(n).filter(|&n| n > R::one()) else {
+                    return R::zero();
+                };
+                gamma * (n.ln() / n).powf(dimension.recip())
+            }
+        }
+    }
+}
+
+impl<R> From<RadiusNeighborhood<R>> for Neighborhood<R> {
+    fn from(value: RadiusNeighborhood<R>) -> Self {
+        match value {
+            RadiusNeighborhood::Radius(r) => Self::Radius(r),
+            RadiusNeighborhood::ShrinkingRadius { gamma, dimension } => {
+                Self::ShrinkingRadius { gamma, dimension }
+            }
+        }
+    }
+}
+
+impl<K, V, NN, R> Neighbors<K, V, NN> for RadiusNeighborhood<R>
+where
+    NN: RangeNearestNeighborsMap<K, V, Distance = R>,
+    R: Float,
+{
+    fn neighbors<'q>(&self, nn: &'q NN, key: &'q K, n: usize) -> Vec<(&'q K, &'q V, R)> {
+        nn.nearest_within_r_sorted(key, self.radius(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn k_star() {
+        let nbhd: Neighborhood<f64> = Neighborhood::KNearestStar { dimension: 2 };
+        assert_eq!(nbhd.k(1), Some(0));
+        // e * 1.5 * ln(100) = 18.78
+        assert_eq!(nbhd.k(100), Some(19));
+        assert_eq!(nbhd.radius(100), None);
+        assert_eq!(Neighborhood::<f64>::KNearest(5).k(100), Some(5));
+    }
+
+    #[test]
+    fn sorted_neighbors() {
+        use crate::{
+            metric::{Metric, SquaredEuclidean},
+            nn::{KdTreeMap, NearestNeighborsMap},
+            space::Vector,
+        };
+
+        let mut nn = KdTreeMap::new(SquaredEuclidean);
+        for (i, x) in [0.3, -0.1, 0.5, 0.2, -0.4, 2.0].into_iter().enumerate() {
+            nn.insert(Vector::new([x]), i);
+        }
+        let q = Vector::new([0.0]);
+        for nbhd in [Neighborhood::Radius(1.0), Neighborhood::KNearest(5)] {
+            let nbrs = nbhd.neighbors(&nn, &q, 6);
+            assert_eq!(
+                nbrs.iter().map(|&(_, &v, _)| v).collect::<Vec<_>>(),
+                [1, 3, 0, 4, 2]
+            );
+            assert!(nbrs
+                .iter()
+                .all(|&(k, _, d)| f64::abs(SquaredEuclidean.distance(k, &q) - d) < 1e-12));
+        }
+    }
+
+    #[test]
+    fn radius_without_k_nearest() {
+        use crate::{
+            metric::SquaredEuclidean,
+            nn::{KdTreeMap, NearestNeighborsMap},
+            space::Vector,
+        };
+
+        /// A map which only supports range queries.
+        struct RangeOnly(KdTreeMap<Vector<1>, usize, SquaredEuclidean>);
+
+        impl NearestNeighborsMap<Vector<1>, usize> for RangeOnly {
+            type Distance = f64;
+
+            fn insert(&mut self, key: Vector<1>, value: usize) {
+                self.0.insert(key, value);
+            }
+
+            fn nearest<'q>(&'q self, key: &Vector<1>) -> Option<(&'q Vector<1>, &'q usize)> {
+                self.0.nearest(key)
+            }
+        }
+
+        impl RangeNearestNeighborsMap<Vector<1>, usize> for RangeOnly {
+            type RangeNearest<'q> =
+                <KdTreeMap<Vector<1>, usize, SquaredEuclidean> as RangeNearestNeighborsMap<
+                    Vector<1>,
+                    usize,
+                >>::RangeNearest<'q>;
+
+            fn nearest_within_r<'q>(
+                &'q self,
+                key: &'q Vector<1>,
+                r: f64,
+            ) -> Self::RangeNearest<'q> {
+                self.0.nearest_within_r(key, r)
+            }
+        }
+
+        let mut nn = RangeOnly(KdTreeMap::new(SquaredEuclidean));
+        for (i, x) in [0.3, -0.1, 0.5, 0.2, -0.4, 2.0].into_iter().enumerate() {
+            nn.insert(Vector::new([x]), i);
+        }
+        let q = Vector::new([0.0]);
+        let nbrs = RadiusNeighborhood::Radius(1.0).neighbors(&nn, &q, 6);
+        assert_eq!(
+            nbrs.iter().map(|&(_, &v, _)| v).collect::<Vec<_>>(),
+            [1, 3, 0, 4, 2]
+        );
+    }
+}
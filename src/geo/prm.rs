@@ -3,9 +3,20 @@ use core::{fmt::Debug, hash::Hash, iter, mem::swap, ops::Add};
 
 use alloc::vec::Vec;
 use num_traits::Zero;
+use rand::Rng;
 
 use crate::{
-    metric::Metric, nn::RangeNearestNeighborsMap, sample::Sample, time::Timeout, valid::GeoValidate,
+    goal::Goal,
+    metric::Metric,
+    nn::{NearestNeighborsMap, RangeNearestNeighborsMap},
+    sample::Sample,
+    time::Timeout,
+    valid::GeoValidate,
+};
+
+use super::{
+    problem::{sample_valid_goal, CountChecks, GoalSample, Recorder},
+    Graph, Neighbors, PlanResult, PlanStatus, Planner, Problem,
 };
 
 /// Probabilistic roadmaps; a class of anytime geometric motion planner.
@@ -14,9 +25,37 @@ use crate::{
 ///
 /// - `C`: The configurations of the robot.
 /// - `NN`: The nearest-neighbor data structure to use. To be useful, `NN` should implement
-///   [`RangeNearestNeighborsMap`].
+///   [`RangeNearestNeighborsMap`], and [`KNearestNeighborsMap`](crate::nn::KNearestNeighborsMap) to
+///   connect nodes by a k-nearest [`Neighborhood`](super::Neighborhood).
 /// - `V`: The state validator. `V` should implement [`EdgeValidate`].
 ///
+/// # Connection strategies
+///
+/// Nodes may be connected to all other nodes within a fixed radius (via [`Prm::insert_r`] and
+/// [`Prm::grow_r`]), or by any [`Neighbors`] strategy (via [`Prm::insert`] and [`Prm::grow`]).
+/// Using [`RadiusNeighborhood::ShrinkingRadius`](super::RadiusNeighborhood::ShrinkingRadius)
+/// yields PRM* and only requires range queries, while
+/// [`Neighborhood::KNearest`](super::Neighborhood::KNearest) and
+/// [`Neighborhood::KNearestStar`](super::Neighborhood::KNearestStar) yield k-PRM and k-PRM*
+/// respectively.
+///
+/// # Lazy evaluation
+///
+/// A PRM constructed with [`Prm::new_lazy`] does not validate edges when inserting nodes.
+/// Instead, [`Prm::lazy_path`] validates only the edges along each candidate path, removing any
+/// invalid edges and replanning until it finds a fully valid path.
+/// The validity of each edge is cached, so every edge is checked at most once.
+///
+/// # Planning
+///
+/// When used as a [`Planner`], the starts of the problem and a configuration sampled from the goal
+/// (if the goal can be sampled) are inserted into the roadmap, connecting nodes within the
+/// problem's radius.
+/// The roadmap then grows until a start is connected to any node satisfying the goal, and the path
+/// with the fewest edges between them is returned.
+/// Since [`Prm::solve`] shares its name with [`Planner::solve`], the latter must be called as
+/// `Planner::solve(&mut prm, ...)`.
+///
 /// # Citation
 ///
 /// ```bibtex
@@ -35,10 +74,14 @@ pub struct Prm<'a, C, NN, V> {
     /// List of configurations for each node.
     configurations: Vec<C>,
     /// Adjacency list of nodes.
-    edges: Vec<Vec<usize>>,
+    edges: Vec<Vec<Edge>>,
+    /// The connected components of the graph.
+    /// In a lazy PRM, this may over-approximate connectivity, since it includes unchecked edges.
     components: SetForest,
     nn: NN,
     valid: &'a V,
+    /// Whether edge validation is deferred until a path is queried.
+    lazy: bool,
 }
 
 mod private {
@@ -46,11 +89,22 @@ mod private {
 }
 use private::Node;
 
-#[expect(clippy::module_name_repetitions)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The ID for a node in a [`Prm`].
 pub struct PrmNodeId(usize);
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// One end of an undirected edge in a [`Prm`].
+struct Edge {
+    /// The node at the other end of the edge.
+    to: usize,
+    /// Whether the edge is known to be valid.
+    /// Edges which are known to be invalid are removed from the graph.
+    checked: bool,
+}
+
 #[derive(Clone, Debug)]
 /// A disjoint set forest.
 /// todo: should we move away from the S-o-A structure? Probably not, this is likely faster.
@@ -75,6 +129,24 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
             components: SetForest::new(),
             nn,
             valid,
+            lazy: false,
+        }
+    }
+
+    #[must_use]
+    /// Construct a new lazy PRM, which defers validating edges until they are used in a path.
+    ///
+    /// Since the connected components of a lazy PRM include unchecked edges,
+    /// [`Prm::grow_r_solve`] and [`Prm::grow_solve`] may stop growing before a valid path
+    /// actually exists.
+    pub const fn new_lazy(nn: NN, valid: &'a V) -> Self {
+        Self {
+            configurations: Vec::new(),
+            edges: Vec::new(),
+            components: SetForest::new(),
+            nn,
+            valid,
+            lazy: true,
         }
     }
 
@@ -129,6 +201,63 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
         }
     }
 
+    /// Grow this PRM until `timeout` runs out, connecting each new node to the nodes in its
+    /// `neighborhood`.
+    /// Generated nodes will only be sampled from `sample` using `rng` as the source of randomness.
+    pub fn grow<NB, TC, S, RNG>(
+        &mut self,
+        neighborhood: &NB,
+        timeout: &mut TC,
+        sample: &S,
+        rng: &mut RNG,
+    ) where
+        V: GeoValidate<C>,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        TC: Timeout,
+        S: Sample<C, RNG>,
+        C: Clone,
+    {
+        while !timeout.is_over() {
+            timeout.update_sample_count(1);
+            let c = sample.sample(rng);
+            if self.insert(c, neighborhood).is_some() {
+                timeout.update_node_count(1);
+            }
+        }
+    }
+
+    /// Grow this PRM while attempting to solve a problem connecting `start` and `goal`, connecting
+    /// each new node to the nodes in its `neighborhood`. This will only terminate when `timeout`
+    /// is over.
+    pub fn grow_solve<NB, TC, S, RNG>(
+        &mut self,
+        neighborhood: &NB,
+        timeout: &mut TC,
+        sample: &S,
+        rng: &mut RNG,
+        start: PrmNodeId,
+        goal: PrmNodeId,
+    ) where
+        V: GeoValidate<C>,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        TC: Timeout,
+        S: Sample<C, RNG>,
+        C: Clone,
+    {
+        while !timeout.is_over() {
+            timeout.update_sample_count(1);
+            let c = sample.sample(rng);
+            if self.insert(c, neighborhood).is_some() {
+                timeout.update_node_count(1);
+            }
+            if self.components.find_cache(start.0) == self.components.find_cache(goal.0) {
+                timeout.notify_solved();
+            }
+        }
+    }
+
     /// Insert a configuration into the graph, connecting it to all other nodes in the graph within
     /// a distance of `radius`. Returns the ID of the node it created, or `None` if the given
     /// configuration was invalid.
@@ -137,24 +266,73 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
         V: GeoValidate<C>,
         NN: RangeNearestNeighborsMap<C, Node, Distance = R>,
         C: Clone,
+    {
+        self.insert_r_with(self.valid, c, radius)
+    }
+
+    /// Insert a configuration into the graph as in [`Prm::insert_r`], checking validity with
+    /// `valid`.
+    fn insert_r_with<W, R>(&mut self, valid: &W, c: C, radius: R) -> Option<PrmNodeId>
+    where
+        W: GeoValidate<C>,
+        NN: RangeNearestNeighborsMap<C, Node, Distance = R>,
+        C: Clone,
+    {
+        if !valid.is_valid_configuration(&c) {
+            return None;
+        }
+
+        let near = self
+            .nn
+            .nearest_within_r(&c, radius)
+            .map(|(_, &Node(n), _)| n)
+            .collect::<Vec<_>>();
+        Some(self.connect(valid, c, near))
+    }
+
+    /// Insert a configuration into the graph, connecting it to all nodes in its `neighborhood`,
+    /// from nearest to farthest.
+    /// Returns the ID of the node it created, or `None` if the given configuration was invalid.
+    pub fn insert<NB>(&mut self, c: C, neighborhood: &NB) -> Option<PrmNodeId>
+    where
+        V: GeoValidate<C>,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        C: Clone,
     {
         if !self.valid.is_valid_configuration(&c) {
             return None;
         }
 
+        let near = neighborhood
+            .neighbors(&self.nn, &c, self.configurations.len() + 1)
+            .into_iter()
+            .map(|(_, &Node(n), _)| n)
+            .collect::<Vec<_>>();
+        Some(self.connect(self.valid, c, near))
+    }
+
+    /// Add a valid configuration `c` to the graph, connecting it to each node in `near` to which
+    /// it has a valid transition according to `valid`.
+    /// If `self` is lazy, `c` is connected to every node in `near` without checking transitions.
+    fn connect<W>(&mut self, valid: &W, c: C, near: Vec<usize>) -> PrmNodeId
+    where
+        W: GeoValidate<C>,
+        NN: NearestNeighborsMap<C, Node>,
+        C: Clone,
+    {
         let i = self.edges.len();
         self.edges.push(Vec::new());
         let new_component = self.components.create();
-        for n in self
-            .nn
-            .nearest_within_r(&c, radius)
-            .map(|&Node(n)| n)
-            .filter(|&n| self.valid.is_valid_transition(&c, &self.configurations[n]))
+        for n in near
+            .into_iter()
+            .filter(|&n| self.lazy || valid.is_valid_transition(&c, &self.configurations[n]))
         {
             self.components.unify(new_component, n);
             // assume bidirectionality
-            self.edges[i].push(n);
-            self.edges[n].push(i);
+            let checked = !self.lazy;
+            self.edges[i].push(Edge { to: n, checked });
+            self.edges[n].push(Edge { to: i, checked });
         }
 
         self.nn.insert(c.clone(), Node(i));
@@ -164,7 +342,7 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
             self.configurations.len(),
             "configuration and edge buffers must have equal length"
         );
-        Some(PrmNodeId(i))
+        PrmNodeId(i)
     }
 
     /// Get the configuration in the graph corresponding to the given node ID.
@@ -174,8 +352,112 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
         self.configurations.get(id.0)
     }
 
+    #[must_use]
+    /// Get the number of nodes in the graph.
+    pub const fn num_nodes(&self) -> usize {
+        self.configurations.len()
+    }
+
+    /// Iterate over the ID and configuration of every node in the graph.
+    pub fn nodes(&self) -> impl Iterator<Item = (PrmNodeId, &C)> {
+        self.configurations
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (PrmNodeId(i), c))
+    }
+
+    /// Iterate over the IDs of the nodes which share an edge with the node with ID `id`.
+    ///
+    /// In a lazy PRM, this includes nodes connected by edges which have not yet been validated.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `id` points to a node which does not exist in `self`.
+    pub fn neighbors(&self, id: PrmNodeId) -> impl Iterator<Item = PrmNodeId> + '_ {
+        self.edges[id.0].iter().map(|e| PrmNodeId(e.to))
+    }
+
+    /// Iterate over every edge in the graph as a pair of node IDs, visiting each undirected edge
+    /// once with the lesser ID first.
+    ///
+    /// In a lazy PRM, this includes edges which have not yet been validated.
+    pub fn edges(&self) -> impl Iterator<Item = (PrmNodeId, PrmNodeId)> + '_ {
+        self.edges.iter().enumerate().flat_map(|(i, adj)| {
+            adj.iter()
+                .filter(move |e| i < e.to)
+                .map(move |e| (PrmNodeId(i), PrmNodeId(e.to)))
+        })
+    }
+
+    #[must_use]
+    /// Get the roadmap as an undirected [`Graph`] for export.
+    /// The index of each node in the graph is the same as its [`PrmNodeId`].
+    ///
+    /// In a lazy PRM, this includes edges which have not yet been validated.
+    pub fn graph(&self) -> Graph<'_, C> {
+        Graph::new(
+            self.configurations.iter().collect(),
+            self.edges().map(|(a, b)| (a.0, b.0)).collect(),
+            false,
+        )
+    }
+
+    #[must_use]
+    /// Determine whether the nodes with IDs `a` and `b` are in the same connected component.
+    ///
+    /// In a lazy PRM, this may over-approximate connectivity, since components include edges
+    /// which have not yet been validated.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `a` or `b` point to nodes which do not exist in `self`.
+    pub fn connected(&self, a: PrmNodeId, b: PrmNodeId) -> bool {
+        self.components.find(a.0) == self.components.find(b.0)
+    }
+
+    #[must_use]
+    /// Get the number of nodes in the connected component containing the node with ID `id`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `id` points to a node which does not exist in `self`.
+    pub fn component_size(&self, id: PrmNodeId) -> usize {
+        self.components.sizes[self.components.find(id.0)]
+    }
+
+    #[must_use]
+    /// Get the number of connected components in the graph.
+    pub fn num_components(&self) -> usize {
+        (0..self.components.parents.len())
+            .filter(|&i| self.components.parents[i] == i)
+            .count()
+    }
+
+    #[must_use]
+    /// Get the IDs of the nodes in each connected component of the graph.
+    ///
+    /// Components are ordered by their least node ID, and the nodes within each component are in
+    /// increasing order.
+    pub fn components(&self) -> Vec<Vec<PrmNodeId>> {
+        // index of the component containing each representative node, if seen yet
+        let mut index = vec![usize::MAX; self.configurations.len()];
+        let mut components: Vec<Vec<PrmNodeId>> = Vec::new();
+        for i in 0..self.configurations.len() {
+            let root = self.components.find(i);
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[index[root]].push(PrmNodeId(i));
+        }
+        components
+    }
+
     /// Compute a path between `start` and `end`.
     ///
+    /// If `self` is lazy, the path may traverse edges which have not been validated; use
+    /// [`Prm::lazy_path`] to validate them.
+    ///
     /// # Panics
     ///
     /// This function may panic if `start` or `end` point to nodes which do not exist in `self`.
@@ -184,7 +466,48 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
         M: Metric<C, Distance = D>,
         D: Add + Zero + PartialOrd + Clone,
     {
-        // use A*
+        if !self.same_component(start, end) {
+            // different components - no solution exists
+            return None;
+        }
+
+        self.astar(start, end, cost)
+    }
+
+    /// Compute a valid path between `start` and `end`.
+    ///
+    /// If `self` is lazy, this validates the unchecked edges along each candidate path, removing
+    /// any invalid edges from the graph and replanning until a valid path is found.
+    /// Otherwise, this is equivalent to [`Prm::path`].
+    ///
+    /// # Panics
+    ///
+    /// This function may panic if `start` or `end` point to nodes which do not exist in `self`.
+    pub fn lazy_path<M, D>(
+        &mut self,
+        start: PrmNodeId,
+        end: PrmNodeId,
+        cost: &M,
+    ) -> Option<Vec<PrmNodeId>>
+    where
+        V: GeoValidate<C>,
+        M: Metric<C, Distance = D>,
+        D: Add + Zero + PartialOrd + Clone,
+    {
+        if !self.same_component(start, end) {
+            // different components - no solution exists
+            return None;
+        }
+
+        self.search(self.valid, start, end, cost)
+    }
+
+    /// Determine whether `start` and `end` are in the same connected component.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `start` or `end` point to nodes which do not exist in `self`.
+    fn same_component(&self, start: PrmNodeId, end: PrmNodeId) -> bool {
         assert!(
             (0..self.configurations.len()).contains(&start.0),
             "invalid start configuration ID"
@@ -194,11 +517,144 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
             "invalid end configuration ID"
         );
 
-        if self.components.find(start.0) != self.components.find(end.0) {
-            // different components - no solution exists
+        self.components.find(start.0) == self.components.find(end.0)
+    }
+
+    /// Find a path between two arbitrary configurations `start` and `goal`, which need not be
+    /// nodes in the roadmap.
+    ///
+    /// If the direct transition from `start` to `goal` is valid, it is returned without consulting
+    /// the roadmap.
+    /// Otherwise, `start` and `goal` are temporarily connected to the nodes in their
+    /// `neighborhood`s, and are removed once the search is complete, so repeated queries do not
+    /// grow the roadmap.
+    /// Returns the sequence of configurations from `start` to `goal`, or `None` if either is
+    /// invalid or no path between them exists.
+    pub fn solve<NB, M, D>(
+        &mut self,
+        start: C,
+        goal: C,
+        neighborhood: &NB,
+        cost: &M,
+    ) -> Option<Vec<C>>
+    where
+        V: GeoValidate<C>,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        M: Metric<C, Distance = D>,
+        D: Add + Zero + PartialOrd + Clone,
+        C: Clone,
+    {
+        if !self.valid.is_valid_configuration(&start) || !self.valid.is_valid_configuration(&goal) {
+            return None;
+        }
+        if self.valid.is_valid_transition(&start, &goal) {
+            return Some(vec![start, goal]);
+        }
+        let start_near = self.valid_neighbors(&start, neighborhood);
+        let goal_near = self.valid_neighbors(&goal, neighborhood);
+        if !start_near.iter().any(|&a| {
+            goal_near
+                .iter()
+                .any(|&b| self.components.find(a) == self.components.find(b))
+        }) {
+            // start and goal do not connect to a common component
             return None;
         }
 
+        // attach start and goal as temporary nodes at the end of the roadmap
+        let n = self.configurations.len();
+        self.configurations.push(start);
+        self.configurations.push(goal);
+        for (id, near) in [(n, &start_near), (n + 1, &goal_near)] {
+            self.edges
+                .push(near.iter().map(|&to| Edge { to, checked: true }).collect());
+            for &m in near {
+                self.edges[m].push(Edge {
+                    to: id,
+                    checked: true,
+                });
+            }
+        }
+
+        let path = self
+            .search(self.valid, PrmNodeId(n), PrmNodeId(n + 1), cost)
+            .map(|ids| {
+                ids.into_iter()
+                    .map(|PrmNodeId(i)| self.configurations[i].clone())
+                    .collect()
+            });
+
+        // detach the temporary nodes
+        for &m in start_near.iter().chain(&goal_near) {
+            self.edges[m].retain(|e| e.to < n);
+        }
+        self.edges.truncate(n);
+        self.configurations.truncate(n);
+        path
+    }
+
+    /// Get the IDs of all nodes in the `neighborhood` of `c` to which `c` has a valid transition.
+    fn valid_neighbors<NB>(&self, c: &C, neighborhood: &NB) -> Vec<usize>
+    where
+        V: GeoValidate<C>,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+    {
+        neighborhood
+            .neighbors(&self.nn, c, self.configurations.len() + 1)
+            .into_iter()
+            .map(|(_, &Node(n), _)| n)
+            .filter(|&n| self.valid.is_valid_transition(c, &self.configurations[n]))
+            .collect()
+    }
+
+    /// Search for a path between `start` and `end`, validating the edges along candidate paths
+    /// with `valid` if `self` is lazy.
+    fn search<W, M, D>(
+        &mut self,
+        valid: &W,
+        start: PrmNodeId,
+        end: PrmNodeId,
+        cost: &M,
+    ) -> Option<Vec<PrmNodeId>>
+    where
+        W: GeoValidate<C>,
+        M: Metric<C, Distance = D>,
+        D: Add + Zero + PartialOrd + Clone,
+    {
+        'replan: loop {
+            let path = self.astar(start, end, cost)?;
+            for pair in path.windows(2) {
+                let (a, b) = (pair[0].0, pair[1].0);
+                let Some(i) = self.edges[a].iter().position(|e| e.to == b) else {
+                    unreachable!("A* must only traverse extant edges");
+                };
+                if self.edges[a][i].checked {
+                    continue;
+                }
+                if valid.is_valid_transition(&self.configurations[a], &self.configurations[b]) {
+                    self.edges[a][i].checked = true;
+                    for e in self.edges[b].iter_mut().filter(|e| e.to == a) {
+                        e.checked = true;
+                    }
+                } else {
+                    self.edges[a].swap_remove(i);
+                    self.edges[b].retain(|e| e.to != a);
+                    continue 'replan;
+                }
+            }
+            return Some(path);
+        }
+    }
+
+    /// Search for the cheapest path between `start` and `end` using A*, traversing both checked
+    /// and unchecked edges.
+    fn astar<M, D>(&self, start: PrmNodeId, end: PrmNodeId, cost: &M) -> Option<Vec<PrmNodeId>>
+    where
+        M: Metric<C, Distance = D>,
+        D: Add + Zero + PartialOrd + Clone,
+    {
         // open may include duplicate entries if we find alternate paths to open nodes
         let mut open = BinaryHeap::new();
         let mut parent = vec![0; self.configurations.len()];
@@ -228,16 +684,13 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
             }
             let nc = &self.configurations[node];
 
-            for &neighbor in &self.edges[node] {
+            for &Edge { to: neighbor, .. } in &self.edges[node] {
                 let nbr_c = &self.configurations[neighbor];
                 let new_g_score = cost.distance(nbr_c, nc)
                     + g_score[node]
                         .clone()
                         .expect("nodes in open set must have extant g-score");
-                if g_score[neighbor]
-                    .as_ref()
-                    .map_or(true, |d| &new_g_score < d)
-                {
+                if g_score[neighbor].as_ref().is_none_or(|d| &new_g_score < d) {
                     // found a shorter path to neighbor
                     parent[neighbor] = node;
                     g_score[neighbor] = Some(new_g_score.clone());
@@ -249,7 +702,112 @@ impl<'a, C, NN, V> Prm<'a, C, NN, V> {
             }
         }
 
-        unreachable!("if start and goal are in same connected component, A* must find a path");
+        // the components of a lazy PRM may contain invalid edges which have since been removed
+        debug_assert!(
+            self.lazy,
+            "if start and goal are in same connected component, A* must find a path"
+        );
+        None
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+/// A borrowed view of the roadmap of a [`Prm`], used for serialization.
+struct RoadmapRef<'r, C> {
+    configurations: &'r [C],
+    edges: &'r [Vec<Edge>],
+    lazy: bool,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+/// The owned roadmap of a [`Prm`], used for deserialization.
+struct Roadmap<C> {
+    configurations: Vec<C>,
+    edges: Vec<Vec<Edge>>,
+    lazy: bool,
+}
+
+#[cfg(feature = "serde")]
+/// A `Prm` is serialized as its roadmap: its configurations and edges.
+/// Its nearest-neighbor structure, connected components, and state validator are not serialized;
+/// use [`Prm::load`] to deserialize a roadmap.
+impl<C, NN, V> serde::Serialize for Prm<'_, C, NN, V>
+where
+    C: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        RoadmapRef {
+            configurations: &self.configurations,
+            edges: &self.edges,
+            lazy: self.lazy,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, C, NN, V> Prm<'a, C, NN, V> {
+    /// Deserialize a roadmap which was previously serialized from a `Prm`.
+    ///
+    /// `nn` should be an empty nearest-neighbor structure; it will be rebuilt from the roadmap's
+    /// configurations, and the connected components are rebuilt from its edges.
+    /// `valid` is the state validator for the loaded PRM, and should be equivalent to the one
+    /// used to build the roadmap.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `deserializer` fails or if the deserialized roadmap
+    /// is inconsistent.
+    pub fn load<'de, D>(deserializer: D, mut nn: NN, valid: &'a V) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+        C: serde::Deserialize<'de> + Clone,
+        NN: NearestNeighborsMap<C, Node>,
+    {
+        use serde::{de::Error, Deserialize};
+
+        let Roadmap {
+            configurations,
+            edges,
+            lazy,
+        } = Roadmap::<C>::deserialize(deserializer)?;
+
+        let n = configurations.len();
+        if edges.len() != n {
+            return Err(D::Error::custom(
+                "roadmap configurations and edges must have equal length",
+            ));
+        }
+        if edges.iter().flatten().any(|e| e.to >= n) {
+            return Err(D::Error::custom(
+                "roadmap contains an out-of-bounds node ID",
+            ));
+        }
+
+        let mut components = SetForest::new();
+        for (i, c) in configurations.iter().enumerate() {
+            nn.insert(c.clone(), Node(i));
+            components.create();
+        }
+        for (i, node_edges) in edges.iter().enumerate() {
+            for e in node_edges {
+                components.unify(i, e.to);
+            }
+        }
+
+        Ok(Self {
+            configurations,
+            edges,
+            components,
+            nn,
+            valid,
+            lazy,
+        })
     }
 }
 
@@ -318,6 +876,104 @@ impl SetForest {
     }
 }
 
+/// A metric which measures the cost of a path by its number of edges.
+struct Hops;
+
+impl<C> Metric<C> for Hops {
+    type Distance = usize;
+
+    fn distance(&self, _: &C, _: &C) -> Self::Distance {
+        1
+    }
+}
+
+impl<'a, C, NN, V, SP, G, R, RNG> Planner<'a, C, V, SP, G, R, RNG> for Prm<'a, C, NN, V>
+where
+    V: GeoValidate<C>,
+    SP: Sample<C, RNG>,
+    G: Goal<C>,
+    NN: RangeNearestNeighborsMap<C, Node, Distance = R>,
+    R: Clone,
+    C: Clone,
+    RNG: Rng,
+{
+    fn solve<TC: Timeout>(
+        &mut self,
+        problem: &Problem<'a, C, V, SP, G, R>,
+        timeout: &mut TC,
+        rng: &mut RNG,
+    ) -> PlanResult<C, G::Distance> {
+        self.valid = problem.valid;
+        let valid = CountChecks::new(problem.valid);
+        let mut timeout = Recorder::new(timeout);
+        let starts: Vec<PrmNodeId> = problem
+            .starts
+            .iter()
+            .filter_map(|c| self.insert_r_with(&valid, c.clone(), problem.radius.clone()))
+            .collect();
+        if starts.is_empty() {
+            return timeout.finish(PlanStatus::InvalidStart, None, None, valid.count());
+        }
+        let mut goals: Vec<PrmNodeId> = (0..self.configurations.len())
+            .filter(|&i| problem.goal.is_satisfied(&self.configurations[i]))
+            .map(PrmNodeId)
+            .collect();
+        // goals which cannot be sampled validly may still be reached while exploring
+        match sample_valid_goal(&problem.goal, &valid, rng) {
+            GoalSample::Valid(c) => {
+                goals.extend(self.insert_r_with(&valid, c, problem.radius.clone()));
+            }
+            GoalSample::Invalid if goals.is_empty() => {
+                return timeout.finish(PlanStatus::InvalidGoal, None, None, valid.count());
+            }
+            _ => (),
+        }
+
+        // a failed search means that no path exists through the roadmap (in a lazy roadmap, its
+        // invalid edges have been removed), so only search again once the roadmap has grown
+        let mut grown = true;
+        loop {
+            if grown {
+                for &goal in &goals {
+                    for &start in &starts {
+                        if self.components.find_cache(start.0) != self.components.find_cache(goal.0)
+                        {
+                            continue;
+                        }
+                        if let Some(ids) = self.search(&valid, start, goal, &Hops) {
+                            timeout.notify_solved();
+                            let path = ids
+                                .into_iter()
+                                .map(|PrmNodeId(i)| self.configurations[i].clone())
+                                .collect();
+                            return timeout.finish(
+                                PlanStatus::Exact,
+                                Some(path),
+                                Some(G::Distance::zero()),
+                                valid.count(),
+                            );
+                        }
+                    }
+                }
+            }
+            if timeout.is_over() {
+                return timeout.finish(PlanStatus::Timeout, None, None, valid.count());
+            }
+            timeout.update_sample_count(1);
+            let c = problem.space_sampler.sample(rng);
+            let satisfies = problem.goal.is_satisfied(&c);
+            let inserted = self.insert_r_with(&valid, c, problem.radius.clone());
+            grown = inserted.is_some();
+            if let Some(id) = inserted {
+                timeout.update_node_count(1);
+                if satisfies {
+                    goals.push(id);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 struct Open<D> {
     f_score: D,
@@ -344,12 +1000,13 @@ impl<D: PartialOrd + PartialEq> Ord for Open<D> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        geo::Neighborhood,
         metric::{Metric, SquaredEuclidean},
         nn::KdTreeMap,
         sample::Rectangle,
         space::Vector,
         time::{LimitNodes, Solved},
-        valid::AlwaysValid,
+        valid::{AlwaysValid, SampleInterpolate},
     };
     use alloc::vec::Vec;
     use rand::SeedableRng;
@@ -437,4 +1094,342 @@ mod tests {
             "all transitions must be within growth radius"
         );
     }
+
+    #[test]
+    fn prm_star() {
+        // squared metric: square gamma and halve the dimension
+        let nbhd = Neighborhood::ShrinkingRadius {
+            gamma: 1.0,
+            dimension: 1.0,
+        };
+        let mut prm: Prm<Vector<2>, _, _> =
+            Prm::new(KdTreeMap::new(SquaredEuclidean), &AlwaysValid);
+        let start = prm.insert(Vector::new([0.0, 0.0]), &nbhd).unwrap();
+        let end = prm.insert(Vector::new([1.0, 1.0]), &nbhd).unwrap();
+        prm.grow_solve(
+            &nbhd,
+            &mut Solved::new(),
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &mut ChaCha20Rng::seed_from_u64(2707),
+            start,
+            end,
+        );
+        assert!(prm.path(start, end, &SquaredEuclidean).is_some());
+    }
+
+    #[test]
+    fn k_prm_star() {
+        let nbhd = Neighborhood::KNearestStar { dimension: 2 };
+        let mut prm: Prm<Vector<2>, _, _> =
+            Prm::new(KdTreeMap::new(SquaredEuclidean), &AlwaysValid);
+        let start = prm.insert(Vector::new([0.0, 0.0]), &nbhd).unwrap();
+        let end = prm.insert(Vector::new([1.0, 1.0]), &nbhd).unwrap();
+        prm.grow(
+            &nbhd,
+            &mut LimitNodes::new(200),
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        assert!(prm.path(start, end, &SquaredEuclidean).is_some());
+
+        // the most recent node connected to all of its k nearest neighbors
+        let n = prm.configurations.len();
+        let k = nbhd.k(n).unwrap();
+        assert!(prm.edges[n - 1].len() >= k);
+    }
+
+    #[test]
+    fn lazy() {
+        use crate::valid::GeoValidate;
+        use core::cell::Cell;
+
+        let checks = Cell::new(0);
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| {
+                checks.set(checks.get() + 1);
+                !(0.45..0.55).contains(&c[0]) || c[1] > 0.8
+            },
+            1e-4,
+        );
+        let r = 0.02;
+        let mut prm: Prm<Vector<2>, _, _> = Prm::new_lazy(KdTreeMap::new(SquaredEuclidean), &wall);
+        let start = prm.insert_r(Vector::new([0.1, 0.1]), r).unwrap();
+        let end = prm.insert_r(Vector::new([0.9, 0.1]), r).unwrap();
+        prm.grow_r(
+            r,
+            &mut LimitNodes::new(1_000),
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+
+        let insert_checks = checks.get();
+        let path = prm
+            .lazy_path(start, end, &SquaredEuclidean)
+            .expect("unable to find path");
+        assert!(path.windows(2).all(|a| wall.is_valid_transition(
+            prm.configuration(a[0]).unwrap(),
+            prm.configuration(a[1]).unwrap()
+        )));
+
+        // a repeated query reuses the cached validity of each edge
+        let path_checks = checks.get();
+        assert!(path_checks > insert_checks);
+        assert_eq!(prm.lazy_path(start, end, &SquaredEuclidean), Some(path));
+        assert_eq!(checks.get(), path_checks);
+    }
+
+    #[test]
+    fn solve() {
+        use crate::valid::GeoValidate;
+
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| !(0.45..0.55).contains(&c[0]) || c[1] > 0.8,
+            1e-4,
+        );
+        let nbhd = Neighborhood::KNearest(10);
+        let mut prm: Prm<Vector<2>, _, _> = Prm::new(KdTreeMap::new(SquaredEuclidean), &wall);
+        prm.grow(
+            &nbhd,
+            &mut LimitNodes::new(500),
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        let edges = prm.edges.clone();
+
+        for (start, goal) in [
+            ([0.1, 0.1], [0.9, 0.1]),
+            ([0.2, 0.5], [0.7, 0.3]),
+            ([0.9, 0.9], [0.1, 0.9]),
+        ] {
+            let (start, goal) = (Vector::new(start), Vector::new(goal));
+            let traj = prm
+                .solve(start, goal, &nbhd, &SquaredEuclidean)
+                .expect("unable to find path");
+            assert_eq!(traj.first(), Some(&start));
+            assert_eq!(traj.last(), Some(&goal));
+            assert!(traj
+                .windows(2)
+                .all(|a| wall.is_valid_transition(&a[0], &a[1])));
+        }
+
+        // queries must not modify the roadmap
+        assert_eq!(prm.configurations.len(), 500);
+        assert_eq!(prm.edges, edges);
+
+        assert_eq!(
+            prm.solve(
+                Vector::new([0.5, 0.5]),
+                Vector::new([0.9, 0.1]),
+                &nbhd,
+                &SquaredEuclidean
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn solve_direct() {
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| !(0.45..0.55).contains(&c[0]) || c[1] > 0.8,
+            1e-4,
+        );
+        let nbhd = Neighborhood::KNearest(10);
+        let mut prm: Prm<Vector<2>, _, _> = Prm::new(KdTreeMap::new(SquaredEuclidean), &wall);
+
+        // an empty roadmap can still connect start and goal directly
+        let (start, goal) = (Vector::new([0.1, 0.1]), Vector::new([0.3, 0.7]));
+        assert_eq!(
+            prm.solve(start, goal, &nbhd, &SquaredEuclidean),
+            Some(vec![start, goal])
+        );
+        assert_eq!(
+            prm.solve(start, start, &nbhd, &SquaredEuclidean),
+            Some(vec![start, start])
+        );
+        assert_eq!(
+            prm.solve(start, Vector::new([0.9, 0.1]), &nbhd, &SquaredEuclidean),
+            None
+        );
+        assert_eq!(prm.num_nodes(), 0);
+    }
+
+    #[test]
+    fn planner() {
+        use crate::{
+            geo::{PlanStatus, Planner, Problem},
+            goal::Goal,
+            valid::GeoValidate,
+        };
+
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| !(0.45..0.55).contains(&c[0]) || c[1] > 0.8,
+            1e-4,
+        );
+        let problem = Problem {
+            starts: vec![Vector::new([0.1, 0.1]), Vector::new([0.5, 0.5])],
+            goal: Vector::new([0.9, 0.1]),
+            space_sampler: Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            valid: &wall,
+            radius: 0.01,
+        };
+        let mut prm: Prm<Vector<2>, _, _> = Prm::new(KdTreeMap::new(SquaredEuclidean), &wall);
+        let res = Planner::solve(
+            &mut prm,
+            &problem,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        assert_eq!(res.status, PlanStatus::Exact);
+        assert_eq!(res.distance_to_goal, Some(0.0));
+        assert!(res.stats.nodes > 0);
+        assert!(res.stats.samples >= res.stats.nodes);
+        assert!(res.stats.validity_checks > res.stats.nodes);
+        #[cfg(feature = "std")]
+        assert!(res.stats.wall_time.is_some());
+        let traj = res.path.expect("unable to find path");
+
+        // the invalid start must not be used
+        assert_eq!(traj.first(), Some(&problem.starts[0]));
+        assert_eq!(traj.last(), Some(&problem.goal));
+        assert!(traj
+            .windows(2)
+            .all(|a| wall.is_valid_transition(&a[0], &a[1])));
+
+        let blocked = Problem {
+            goal: Vector::new([0.5, 0.1]),
+            ..problem
+        };
+        let res = Planner::solve(
+            &mut Prm::new(KdTreeMap::new(SquaredEuclidean), &wall),
+            &blocked,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        assert_eq!(res.status, PlanStatus::InvalidGoal);
+        assert_eq!(res.path, None);
+
+        // a goal region which is mostly blocked by the wall still has valid configurations
+        let region = Problem {
+            starts: vec![Vector::new([0.1, 0.1])],
+            goal: Rectangle {
+                min: Vector::new([0.445, 0.0]),
+                max: Vector::new([0.55, 0.2]),
+            },
+            space_sampler: blocked.space_sampler,
+            valid: &wall,
+            radius: 0.01,
+        };
+        let res = Planner::solve(
+            &mut Prm::new(KdTreeMap::new(SquaredEuclidean), &wall),
+            &region,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        assert_eq!(res.status, PlanStatus::Exact);
+        let traj = res.path.expect("unable to find path");
+        assert!(region.goal.is_satisfied(traj.last().unwrap()));
+    }
+
+    #[test]
+    fn introspect() {
+        // two clusters separated by a wall at x = 0.5
+        let wall = SampleInterpolate::new(|c: &Vector<2>| !(0.45..0.55).contains(&c[0]), 1e-4);
+        let mut prm = Prm::new(KdTreeMap::new(SquaredEuclidean), &wall);
+        let radius = 0.25f64.powi(2);
+        let a = prm.insert_r(Vector::new([0.1, 0.1]), radius).unwrap();
+        let b = prm.insert_r(Vector::new([0.1, 0.3]), radius).unwrap();
+        let c = prm.insert_r(Vector::new([0.3, 0.3]), radius).unwrap();
+        let d = prm.insert_r(Vector::new([0.7, 0.3]), radius).unwrap();
+        assert_eq!(prm.insert_r(Vector::new([0.5, 0.3]), radius), None);
+
+        assert_eq!(prm.num_nodes(), 4);
+        assert_eq!(
+            prm.nodes().map(|(id, _)| id).collect::<Vec<_>>(),
+            [a, b, c, d]
+        );
+        let mut nbrs = prm.neighbors(b).collect::<Vec<_>>();
+        nbrs.sort_unstable();
+        assert_eq!(nbrs, [a, c]);
+        assert_eq!(prm.edges().count(), 2);
+        assert!(prm
+            .edges()
+            .all(|(x, y)| x < y && prm.neighbors(x).any(|n| n == y)));
+
+        assert!(prm.connected(a, c));
+        assert!(!prm.connected(c, d));
+        assert_eq!(prm.component_size(b), 3);
+        assert_eq!(prm.num_components(), 2);
+        assert_eq!(prm.components(), [vec![a, b, c], vec![d]]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_roundtrip() {
+        let r = 0.05;
+        let mut prm: Prm<Vector<2>, _, _> =
+            Prm::new(KdTreeMap::new(SquaredEuclidean), &AlwaysValid);
+        let start = prm.insert_r(Vector::new([0.0, 0.0]), r).unwrap();
+        let end = prm.insert_r(Vector::new([1.0, 1.0]), r).unwrap();
+        prm.grow_r(
+            r,
+            &mut LimitNodes::new(100),
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+
+        let json = serde_json::to_string(&prm).unwrap();
+        let mut loaded: Prm<Vector<2>, _, _> = Prm::load(
+            &mut serde_json::Deserializer::from_str(&json),
+            KdTreeMap::new(SquaredEuclidean),
+            &AlwaysValid,
+        )
+        .unwrap();
+        assert_eq!(loaded.configurations, prm.configurations);
+        assert_eq!(loaded.edges, prm.edges);
+        // the components are rebuilt from the edges
+        assert_eq!(loaded.components(), prm.components());
+        assert_eq!(
+            loaded.path(start, end, &SquaredEuclidean),
+            prm.path(start, end, &SquaredEuclidean)
+        );
+
+        // the nearest-neighbor structure is rebuilt on load
+        let c = Vector::new([0.5, 0.5]);
+        assert_eq!(loaded.insert_r(c, r), prm.insert_r(c, r));
+        assert_eq!(loaded.edges, prm.edges);
+
+        for json in [
+            r#"{"configurations":[[0.0,0.0]],"edges":[],"lazy":false}"#,
+            r#"{"configurations":[[0.0,0.0]],"edges":[[{"to":1,"checked":true}]],"lazy":false}"#,
+        ] {
+            assert!(Prm::<Vector<2>, _, _>::load(
+                &mut serde_json::Deserializer::from_str(json),
+                KdTreeMap::new(SquaredEuclidean),
+                &AlwaysValid,
+            )
+            .is_err());
+        }
+    }
 }
@@ -0,0 +1,310 @@
+use alloc::vec::Vec;
+use core::{cell::Cell, time::Duration};
+
+use rand::Rng;
+
+use crate::{
+    goal::Goal,
+    time::Timeout,
+    valid::{GeoValidate, Validate},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A geometric motion planning problem, which can be solved by any [`Planner`].
+///
+/// # Generic parameters
+///
+/// - `C`: The configurations of the robot.
+/// - `V`: The state validator. Planners typically require that `V` implement
+///   [`GeoValidate`](crate::valid::GeoValidate).
+/// - `SP`: A sampler for states in the configuration space.
+/// - `G`: The goal. Planners typically require that `G` implement [`Goal`](crate::goal::Goal).
+/// - `R`: The step size of the planner.
+pub struct Problem<'a, C, V, SP, G, R> {
+    /// The configurations from which a path may start.
+    /// A path need only start at one of them.
+    pub starts: Vec<C>,
+    /// The goal, which a path must end at a configuration satisfying.
+    pub goal: G,
+    /// A sampler for states in the configuration space.
+    pub space_sampler: SP,
+    /// The state validator.
+    pub valid: &'a V,
+    /// The step size of the planner.
+    ///
+    /// Trees are extended by at most `radius` at a time, while roadmaps connect all nodes within
+    /// `radius` of one another.
+    pub radius: R,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The outcome of solving a [`Problem`] with a [`Planner`].
+pub enum PlanStatus {
+    /// A path was found from a start to a configuration satisfying the goal.
+    Exact,
+    /// The timeout was over before reaching the goal, but a path was found to a configuration
+    /// near the goal.
+    Approximate,
+    /// The timeout was over before any path was found.
+    Timeout,
+    /// None of the starts of the problem were valid configurations.
+    InvalidStart,
+    /// The goal is a single configuration (see [`Goal::is_single_state`]), which is invalid.
+    InvalidGoal,
+    /// The planner requires configurations sampled from the goal, but the goal cannot be sampled.
+    UnsupportedGoal,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Statistics about the work a [`Planner`] performed while solving a [`Problem`].
+pub struct PlanStats {
+    /// The number of configurations sampled, including invalid ones.
+    pub samples: usize,
+    /// The number of nodes added to the planner's tree or roadmap.
+    pub nodes: usize,
+    /// The number of configuration and transition validity checks performed.
+    pub validity_checks: usize,
+    /// The wall time spent solving the problem.
+    /// This is only measured with the `std` feature enabled, and is `None` otherwise.
+    pub wall_time: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The result of solving a [`Problem`] with a [`Planner`].
+///
+/// `D` is the distance between a configuration and the goal, as given by
+/// [`Goal::distance_to_goal`].
+pub struct PlanResult<C, D> {
+    /// The outcome of planning.
+    pub status: PlanStatus,
+    /// The path from one of the starts of the problem to the end configuration, if one was found.
+    ///
+    /// If `status` is [`PlanStatus::Exact`], the path ends at a configuration satisfying the
+    /// goal; if it is [`PlanStatus::Approximate`], the path ends at the configuration nearest to
+    /// the goal which the planner reached.
+    pub path: Option<Vec<C>>,
+    /// The distance from the end of `path` to the goal, if a path was found.
+    pub distance_to_goal: Option<D>,
+    /// Statistics about the work performed by the planner.
+    pub stats: PlanStats,
+}
+
+impl<C, D> PlanResult<C, D> {
+    #[must_use]
+    /// Return `true` if a path to a configuration satisfying the goal was found.
+    pub fn is_exact(&self) -> bool {
+        self.status == PlanStatus::Exact
+    }
+}
+
+/// A motion planner which can solve any [`Problem`].
+///
+/// This allows planners to be swapped without any per-planner code:
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Rng;
+/// use rumple::{
+///     geo::{Planner, Problem, Rrt, RrtConnect},
+///     metric::SquaredEuclidean,
+///     nn::KdTreeMap,
+///     sample::Rectangle,
+///     space::Vector,
+///     time::Solved,
+///     valid::AlwaysValid,
+/// };
+///
+/// type Vec2Problem<'a> =
+///     Problem<'a, Vector<2>, AlwaysValid, Rectangle<Vector<2>>, Vector<2>, f64>;
+///
+/// fn plan<'a, P>(planner: &mut P, problem: &Vec2Problem<'a>) -> bool
+/// where
+///     P: Planner<'a, Vector<2>, AlwaysValid, Rectangle<Vector<2>>, Vector<2>, f64, ChaCha20Rng>,
+/// {
+///     let mut rng = ChaCha20Rng::seed_from_u64(2707);
+///     planner
+///         .solve(problem, &mut Solved::new(), &mut rng)
+///         .path
+///         .is_some()
+/// }
+///
+/// let start = Vector::new([0.0, 0.0]);
+/// let goal = Vector::new([1.0, 1.0]);
+/// let problem = Problem {
+///     starts: vec![start],
+///     goal,
+///     space_sampler: Rectangle {
+///         min: Vector::new([0.0; 2]),
+///         max: Vector::new([1.0; 2]),
+///     },
+///     valid: &AlwaysValid,
+///     radius: 0.01,
+/// };
+/// let mut rrt = Rrt::new(start, KdTreeMap::new(SquaredEuclidean), &AlwaysValid);
+/// assert!(plan(&mut rrt, &problem));
+/// let mut rrtc = RrtConnect::new(
+///     KdTreeMap::new(SquaredEuclidean),
+///     start,
+///     &goal,
+///     &AlwaysValid,
+///     &mut ChaCha20Rng::seed_from_u64(2707),
+/// );
+/// assert!(plan(&mut rrtc, &problem));
+/// ```
+pub trait Planner<'a, C, V, SP, G, R, RNG>
+where
+    G: Goal<C>,
+{
+    /// Attempt to solve `problem` until `timeout` is over, using `rng` as the source of
+    /// randomness.
+    ///
+    /// The planner validates states with `problem.valid` instead of the validator it was
+    /// constructed with.
+    /// Tree planners (such as [`Rrt`](super::Rrt) and [`RrtConnect`](super::RrtConnect)) discard
+    /// their trees and re-root them at the starts of `problem`, so that every path begins at one of
+    /// them.
+    /// Roadmap planners (such as [`Prm`](super::Prm)) retain their roadmap, and the starts of
+    /// `problem` are added to it.
+    fn solve<TC: Timeout>(
+        &mut self,
+        problem: &Problem<'a, C, V, SP, G, R>,
+        timeout: &mut TC,
+        rng: &mut RNG,
+    ) -> PlanResult<C, G::Distance>;
+}
+
+/// A wrapper around the timeout of a [`Planner`] which counts its samples and nodes and measures
+/// the wall time spent planning.
+pub(super) struct Recorder<'t, TC> {
+    timeout: &'t mut TC,
+    stats: PlanStats,
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl<'t, TC> Recorder<'t, TC> {
+    /// Begin recording the statistics of a plan which will run until `timeout` is over.
+    pub fn new(timeout: &'t mut TC) -> Self {
+        Self {
+            timeout,
+            stats: PlanStats::default(),
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    // measuring the wall time with `std` cannot be done in a `const fn`
+    #[cfg_attr(not(feature = "std"), expect(clippy::missing_const_for_fn))]
+    /// Finish recording, producing the result of a plan which performed `validity_checks` checks.
+    pub fn finish<C, D>(
+        mut self,
+        status: PlanStatus,
+        path: Option<Vec<C>>,
+        distance_to_goal: Option<D>,
+        validity_checks: usize,
+    ) -> PlanResult<C, D> {
+        self.stats.validity_checks = validity_checks;
+        #[cfg(feature = "std")]
+        {
+            self.stats.wall_time = Some(self.start.elapsed());
+        }
+        PlanResult {
+            status,
+            path,
+            distance_to_goal,
+            stats: self.stats,
+        }
+    }
+}
+
+impl<TC: Timeout> Timeout for Recorder<'_, TC> {
+    fn is_over(&self) -> bool {
+        self.timeout.is_over()
+    }
+
+    fn update_sample_count(&mut self, n: usize) {
+        self.stats.samples += n;
+        self.timeout.update_sample_count(n);
+    }
+
+    fn update_node_count(&mut self, n: usize) {
+        self.stats.nodes += n;
+        self.timeout.update_node_count(n);
+    }
+
+    fn notify_solved(&mut self) {
+        self.timeout.notify_solved();
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A reference to a state validator which counts the validity checks made through it.
+pub(super) struct CountChecks<'a, V> {
+    valid: &'a V,
+    count: Cell<usize>,
+}
+
+impl<'a, V> CountChecks<'a, V> {
+    pub const fn new(valid: &'a V) -> Self {
+        Self {
+            valid,
+            count: Cell::new(0),
+        }
+    }
+
+    /// Get the number of validity checks made since this was constructed.
+    pub const fn count(&self) -> usize {
+        self.count.get()
+    }
+}
+
+impl<C, V: Validate<C>> Validate<C> for CountChecks<'_, V> {
+    fn is_valid_configuration(&self, c: &C) -> bool {
+        self.count.set(self.count.get() + 1);
+        self.valid.is_valid_configuration(c)
+    }
+}
+
+impl<C, V: GeoValidate<C>> GeoValidate<C> for CountChecks<'_, V> {
+    fn is_valid_transition(&self, start: &C, end: &C) -> bool {
+        self.count.set(self.count.get() + 1);
+        self.valid.is_valid_transition(start, end)
+    }
+}
+
+/// The number of configurations a [`Planner`] samples from a goal while looking for a valid one.
+pub(super) const GOAL_SAMPLE_ATTEMPTS: usize = 100;
+
+/// The outcome of sampling a valid configuration from a goal.
+pub(super) enum GoalSample<C> {
+    /// A valid configuration satisfying the goal.
+    Valid(C),
+    /// The goal is a single configuration, which is invalid.
+    Invalid,
+    /// Every sampled configuration was invalid, but other configurations may satisfy the goal.
+    Missed,
+    /// The goal cannot be sampled.
+    Unsupported,
+}
+
+/// Sample a configuration from `goal` which is valid according to `valid`, making up to
+/// [`GOAL_SAMPLE_ATTEMPTS`] attempts.
+pub(super) fn sample_valid_goal<C, G, W, RNG>(goal: &G, valid: &W, rng: &mut RNG) -> GoalSample<C>
+where
+    G: Goal<C>,
+    W: Validate<C>,
+    RNG: Rng,
+{
+    for _ in 0..GOAL_SAMPLE_ATTEMPTS {
+        let Some(c) = goal.sample_goal(rng) else {
+            return GoalSample::Unsupported;
+        };
+        if valid.is_valid_configuration(&c) {
+            return GoalSample::Valid(c);
+        }
+        if goal.is_single_state() {
+            return GoalSample::Invalid;
+        }
+    }
+    GoalSample::Missed
+}
@@ -1,11 +1,19 @@
 use crate::{
-    nn::NearestNeighborsMap,
+    goal::Goal,
+    nn::{DynamicNearestNeighborsMap, NearestNeighborsMap},
     sample::Sample,
     space::Interpolate,
     time::Timeout,
     valid::{GeoValidate, Validate},
 };
 use alloc::vec::Vec;
+use num_traits::Zero;
+use rand::{distributions::Bernoulli, Rng};
+
+use super::{
+    problem::{sample_valid_goal, CountChecks, GoalSample, Recorder},
+    Graph, PlanResult, PlanStatus, Planner, Problem, Tree,
+};
 
 /// A rapidly-exploring random tree: a geometric single-query sampling-based motion planner.
 ///
@@ -18,6 +26,11 @@ use alloc::vec::Vec;
 ///   `NearestNeighborsMap`.
 /// - `V` should be a state validator; it must implement [`EdgeValidate`] for `C`.
 ///
+/// When used as a [`Planner`], the tree is cleared and rooted at each valid start of the problem,
+/// and the tree grows toward the goal with probability [`Rrt::with_goal_bias`].
+/// If the goal is not reached before the timeout is over, the planner may instead return the path
+/// to the node nearest the goal (see [`Rrt::with_approximate_solutions`]).
+///
 /// # Citation
 ///
 /// ```bibtex
@@ -34,16 +47,22 @@ pub struct Rrt<'a, C, NN, V> {
     /// configurations[0] is the root
     configurations: Vec<C>,
     /// ids for each configuration
-    /// `parent_ids[0]` is ignorable
+    /// the parent of each root is `usize::MAX`
     parent_ids: Vec<usize>,
     /// The nearest neighbors lookup.
     nn: NN,
     /// The state validator.
     valid: &'a V,
+    /// The probability of growing toward the goal when used as a [`Planner`].
+    goal_bias: f64,
+    /// Whether to return an approximate solution when used as a [`Planner`] and the timeout is
+    /// over before reaching the goal.
+    approximate: bool,
 }
 
 /// Workaround module to avoid exposing implementation details of `Node` to consumers.
 mod private {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub struct Node(pub usize);
 }
 use private::Node;
@@ -56,12 +75,12 @@ use private::Node;
 /// - `start`: The start configuration.
 /// - `valid`: The state validator.
 /// - `space_sampler`: A sampler for states in the configuration space.
-/// - `goal`: The goal state or sampler for goal states.
+/// - `goal`: The goal. Planning succeeds once the tree reaches a configuration satisfying it.
 /// - `radius`: The radius by which to expand the RRT.
 /// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
 ///   over.
 /// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
-///   returns `true`, the RRT grows toward the goal instead of to fill the space.
+///   returns `true`, the RRT grows toward a sample from the goal instead of to fill the space.
 /// - `rng`: The source of randomness.
 pub fn rrt<C, NN, V, SP, G, TC, TG, R, RNG>(
     start: C,
@@ -78,14 +97,26 @@ where
     NN: NearestNeighborsMap<C, Node>,
     V: GeoValidate<C>,
     SP: Sample<C, RNG>,
-    G: Sample<C, RNG>,
+    G: Goal<C>,
     R: Clone,
     C: Clone + Interpolate<Distance = R>,
     TC: Timeout,
     TG: Sample<bool, RNG>,
+    RNG: Rng,
 {
+    if !valid.is_valid_configuration(&start) {
+        return None; // invalid configuration
+    }
     let mut rrt = Rrt::new(start, nn, valid);
-    let mut id = rrt.grow_help(space_sampler, goal, radius, timeout, target_goal_distn, rng)?;
+    let mut id = rrt.grow_help(
+        valid,
+        space_sampler,
+        goal,
+        radius,
+        timeout,
+        target_goal_distn,
+        rng,
+    )?;
     let mut traj = Vec::new();
     while id != 0 {
         // can safely remove the configuration since we are deleting the rrt shortly
@@ -112,11 +143,97 @@ impl<'a, C, NN, V> Rrt<'a, C, NN, V> {
             parent_ids: vec![usize::MAX],
             nn,
             valid,
+            goal_bias: 0.05,
+            approximate: false,
+        }
+    }
+
+    #[must_use]
+    /// Set the probability with which this RRT grows toward the goal instead of to fill the space
+    /// when used as a [`Planner`]. The default is 0.05.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `goal_bias` is not between 0 and 1.
+    pub fn with_goal_bias(mut self, goal_bias: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&goal_bias),
+            "goal bias must be a probability"
+        );
+        self.goal_bias = goal_bias;
+        self
+    }
+
+    #[must_use]
+    /// Set whether this RRT returns an approximate solution when used as a [`Planner`] and the
+    /// timeout is over before reaching the goal. The default is `false`.
+    ///
+    /// An approximate solution is the path to the node nearest the goal, as found by
+    /// [`Rrt::approximate_path`], and is reported with [`PlanStatus::Approximate`].
+    pub const fn with_approximate_solutions(mut self, approximate: bool) -> Self {
+        self.approximate = approximate;
+        self
+    }
+
+    /// Get the path from a root of this tree to the node nearest `goal`, along with the distance
+    /// from the end of that path to the goal.
+    ///
+    /// This is useful for making progress toward the goal when [`Rrt::grow_toward`] is unable to
+    /// reach it before its timeout is over, since the tree is retained.
+    /// The node nearest the goal is the one with the least [`Goal::distance_to_goal`], which is
+    /// found by checking every node.
+    pub fn approximate_path<G>(&self, goal: &G) -> (Vec<C>, G::Distance)
+    where
+        G: Goal<C>,
+        C: Clone,
+    {
+        let mut best = 0;
+        let mut best_dist = goal.distance_to_goal(&self.configurations[0]);
+        for (i, c) in self.configurations.iter().enumerate().skip(1) {
+            let dist = goal.distance_to_goal(c);
+            if dist < best_dist {
+                best = i;
+                best_dist = dist;
+            }
+        }
+        (self.path_to(best), best_dist)
+    }
+
+    /// Discard every node of this tree, then root it at each of `roots`.
+    fn reset(&mut self, roots: Vec<C>)
+    where
+        NN: DynamicNearestNeighborsMap<C, Node>,
+        C: Clone,
+    {
+        self.nn.clear();
+        self.parent_ids = vec![usize::MAX; roots.len()];
+        for (i, root) in roots.iter().enumerate() {
+            self.nn.insert(root.clone(), Node(i));
         }
+        self.configurations = roots;
     }
 
-    fn grow_help<SP, G, TC: Timeout, TG, RNG, R>(
+    /// Extract the path from the root of the tree to the node with ID `id`.
+    fn path_to(&self, mut id: usize) -> Vec<C>
+    where
+        C: Clone,
+    {
+        let mut traj = vec![self.configurations[id].clone()];
+        while self.parent_ids[id] != usize::MAX {
+            id = self.parent_ids[id];
+            traj.push(self.configurations[id].clone());
+        }
+        traj.reverse();
+        traj
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    /// Grow this RRT toward `goal`, checking validity with `valid`, and return the ID of a node
+    /// satisfying the goal if one is found.
+    /// The roots of the tree must already be known to be valid.
+    fn grow_help<W, SP, G, TC: Timeout, TG, RNG: Rng, R>(
         &mut self,
+        valid: &W,
         space_sampler: &SP,
         goal: &G,
         radius: R,
@@ -125,35 +242,38 @@ impl<'a, C, NN, V> Rrt<'a, C, NN, V> {
         rng: &mut RNG,
     ) -> Option<usize>
     where
-        V: GeoValidate<C>,
+        W: GeoValidate<C>,
         SP: Sample<C, RNG>,
-        G: Sample<C, RNG>,
+        G: Goal<C>,
         NN: NearestNeighborsMap<C, Node>,
         R: Clone,
         C: Clone + Interpolate<Distance = R>,
         TG: Sample<bool, RNG>,
     {
-        if !self.valid.is_valid_configuration(&self.configurations[0]) {
-            return None; // invalid configuration
+        // the tree may already contain a solution
+        let mut soln = self
+            .configurations
+            .iter()
+            .position(|c| goal.is_satisfied(c));
+        if soln.is_some() {
+            timeout.notify_solved();
         }
-        let mut soln = None;
         while !timeout.is_over() {
             timeout.update_sample_count(1);
-            let sample_goal = target_goal_distn.sample(rng);
-            let target = if sample_goal {
-                goal.sample(rng)
+            let target = if target_goal_distn.sample(rng) {
+                goal.sample_goal(rng)
             } else {
-                space_sampler.sample(rng)
-            };
+                None
+            }
+            .unwrap_or_else(|| space_sampler.sample(rng));
             let (start_cfg, &Node(start_id)) = self
                 .nn
                 .nearest(&target)
                 .expect("NN must always have elements");
-            let (reached, end_cfg) = match start_cfg.interpolate(&target, radius.clone()) {
-                Ok(c) => (false, c),
-                Err(c) => (true, c),
+            let end_cfg = match start_cfg.interpolate(&target, radius.clone()) {
+                Ok(c) | Err(c) => c,
             };
-            if !self.valid.is_valid_transition(start_cfg, &end_cfg) {
+            if !valid.is_valid_transition(start_cfg, &end_cfg) {
                 continue;
             }
             timeout.update_node_count(1);
@@ -165,11 +285,11 @@ impl<'a, C, NN, V> Rrt<'a, C, NN, V> {
                 self.parent_ids.len(),
                 "number of configurations and parents must be equal"
             );
-            self.nn.insert(end_cfg, Node(new_id));
-            if sample_goal && reached {
+            if goal.is_satisfied(&end_cfg) {
                 timeout.notify_solved();
                 soln = Some(new_id);
             }
+            self.nn.insert(end_cfg, Node(new_id));
         }
 
         soln
@@ -180,14 +300,14 @@ impl<'a, C, NN, V> Rrt<'a, C, NN, V> {
     /// # Parameters
     ///
     /// - `space_sampler`: A sampler for states in the configuration space.
-    /// - `goal`: The goal state or sampler for goal states.
+    /// - `goal`: The goal. Planning succeeds once the tree reaches a configuration satisfying it.
     /// - `radius`: The radius by which to expand the RRT.
     /// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
     ///   over.
     /// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
-    ///   returns `true`, the RRT grows toward the goal instead of to fill the space.
+    ///   returns `true`, the RRT grows toward a sample from the goal instead of to fill the space.
     /// - `rng`: The source of randomness.
-    pub fn grow_toward<SP, G, TC, TG, R, RNG>(
+    pub fn grow_toward<SP, G, TC, TG, R, RNG: Rng>(
         &mut self,
         space_sampler: &SP,
         goal: &G,
@@ -199,27 +319,270 @@ impl<'a, C, NN, V> Rrt<'a, C, NN, V> {
     where
         V: GeoValidate<C>,
         SP: Sample<C, RNG>,
-        G: Sample<C, RNG>,
+        G: Goal<C>,
         TG: Sample<bool, RNG>,
         TC: Timeout,
         NN: NearestNeighborsMap<C, Node>,
         R: Clone,
         C: Clone + Interpolate<Distance = R>,
     {
-        let mut id =
-            self.grow_help(space_sampler, goal, radius, timeout, target_goal_distn, rng)?;
-        let mut traj = Vec::new();
-        while id != 0 {
-            traj.push(self.configurations[id].clone());
-            id = self.parent_ids[id];
+        if !self.valid.is_valid_configuration(&self.configurations[0]) {
+            return None; // invalid configuration
         }
-        traj.push(self.configurations[0].clone());
-        traj.reverse();
-        Some(traj)
+        let id = self.grow_help(
+            self.valid,
+            space_sampler,
+            goal,
+            radius,
+            timeout,
+            target_goal_distn,
+            rng,
+        )?;
+        Some(self.path_to(id))
     }
 
     /// Get the number of total nodes in this tree.
-    pub fn num_nodes(&self) -> usize {
+    pub const fn num_nodes(&self) -> usize {
         self.configurations.len()
     }
+
+    #[must_use]
+    /// Get a read-only view of the nodes and edges of this tree.
+    pub fn tree(&self) -> Tree<'_, C> {
+        Tree::new(&self.configurations, &self.parent_ids)
+    }
+
+    #[must_use]
+    /// Get this tree as a directed [`Graph`] for export, with an edge from each node to each of
+    /// its children.
+    pub fn graph(&self) -> Graph<'_, C> {
+        Graph::from(self.tree())
+    }
+}
+
+impl<'a, C, NN, V, SP, G, R, RNG> Planner<'a, C, V, SP, G, R, RNG> for Rrt<'a, C, NN, V>
+where
+    V: GeoValidate<C>,
+    SP: Sample<C, RNG>,
+    G: Goal<C>,
+    NN: DynamicNearestNeighborsMap<C, Node>,
+    R: Clone,
+    C: Clone + Interpolate<Distance = R>,
+    RNG: Rng,
+{
+    fn solve<TC: Timeout>(
+        &mut self,
+        problem: &Problem<'a, C, V, SP, G, R>,
+        timeout: &mut TC,
+        rng: &mut RNG,
+    ) -> PlanResult<C, G::Distance> {
+        self.valid = problem.valid;
+        let valid = CountChecks::new(problem.valid);
+        let mut timeout = Recorder::new(timeout);
+        let starts: Vec<C> = problem
+            .starts
+            .iter()
+            .filter(|c| valid.is_valid_configuration(c))
+            .cloned()
+            .collect();
+        if starts.is_empty() {
+            return timeout.finish(PlanStatus::InvalidStart, None, None, valid.count());
+        }
+        // other goals may still be reached while exploring, even if no valid sample is found
+        if problem.goal.is_single_state()
+            && matches!(
+                sample_valid_goal(&problem.goal, &valid, rng),
+                GoalSample::Invalid
+            )
+        {
+            return timeout.finish(PlanStatus::InvalidGoal, None, None, valid.count());
+        }
+        self.reset(starts);
+
+        let goal_bias = Bernoulli::new(self.goal_bias).expect("goal bias must be a probability");
+        let path = self
+            .grow_help(
+                &valid,
+                &problem.space_sampler,
+                &problem.goal,
+                problem.radius.clone(),
+                &mut timeout,
+                &goal_bias,
+                rng,
+            )
+            .map(|id| self.path_to(id));
+        let checks = valid.count();
+        match path {
+            Some(path) => timeout.finish(
+                PlanStatus::Exact,
+                Some(path),
+                Some(G::Distance::zero()),
+                checks,
+            ),
+            None if self.approximate => {
+                let (path, dist) = self.approximate_path(&problem.goal);
+                timeout.finish(PlanStatus::Approximate, Some(path), Some(dist), checks)
+            }
+            None => timeout.finish(PlanStatus::Timeout, None, None, checks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{
+        goal::Tolerance,
+        metric::{Euclidean, SquaredEuclidean},
+        nn::KdTreeMap,
+        sample::Rectangle,
+        space::Vector,
+        time::{LimitSamples, Solved},
+        valid::SampleInterpolate,
+    };
+
+    #[test]
+    fn approximate() {
+        // the goal is surrounded by a wall at x = 0.5
+        let wall = SampleInterpolate::new(|c: &Vector<2>| !(0.45..0.55).contains(&c[0]), 1e-4);
+        let problem = Problem {
+            starts: vec![Vector::new([0.1, 0.5])],
+            goal: Vector::new([0.9, 0.5]),
+            space_sampler: Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            valid: &wall,
+            radius: 0.01,
+        };
+        let mut rrt = Rrt::new(problem.starts[0], KdTreeMap::new(SquaredEuclidean), &wall);
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let res = rrt.solve(&problem, &mut LimitSamples::new(1000), &mut rng);
+        assert_eq!(res.status, PlanStatus::Timeout);
+        assert_eq!(res.path, None);
+        assert_eq!(res.stats.samples, 1000);
+
+        let mut rrt = rrt.with_approximate_solutions(true);
+        let res = rrt.solve(&problem, &mut LimitSamples::new(1000), &mut rng);
+        assert_eq!(res.status, PlanStatus::Approximate);
+        let path = res.path.expect("approximate path must exist");
+        let dist = res
+            .distance_to_goal
+            .expect("approximate path must have a distance");
+        let end = path.last().unwrap();
+        assert!(end[0] < 0.45 && dist > 0.35);
+        assert!(rrt
+            .configurations
+            .iter()
+            .all(|c| problem.goal.distance_to_goal(c) >= dist));
+        assert!(path
+            .windows(2)
+            .all(|a| wall.is_valid_transition(&a[0], &a[1])));
+    }
+
+    #[test]
+    fn reroot() {
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| !(0.45..0.55).contains(&c[0]) || c[1] > 0.8,
+            1e-4,
+        );
+        let problem = Problem {
+            starts: vec![Vector::new([0.1, 0.5])],
+            goal: Vector::new([0.9, 0.5]),
+            space_sampler: Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            valid: &wall,
+            radius: 0.05,
+        };
+
+        // neither an invalid root nor a root at the goal may be used by the solution
+        for root in [Vector::new([0.5, 0.5]), problem.goal] {
+            let mut rrt = Rrt::new(root, KdTreeMap::new(SquaredEuclidean), &wall);
+            let res = rrt.solve(
+                &problem,
+                &mut Solved::new(),
+                &mut ChaCha20Rng::seed_from_u64(2707),
+            );
+            assert_eq!(res.status, PlanStatus::Exact);
+            let path = res.path.expect("exact solution must have a path");
+            assert_eq!(path.first(), Some(&problem.starts[0]));
+            assert_eq!(path.last(), Some(&problem.goal));
+            assert!(path
+                .windows(2)
+                .all(|a| wall.is_valid_transition(&a[0], &a[1])));
+            assert!(rrt.tree().roots().all(|id| id == 0));
+        }
+    }
+
+    #[test]
+    fn plan_result() {
+        // a wall at x = 0.5
+        let wall = SampleInterpolate::new(|c: &Vector<2>| !(0.45..0.55).contains(&c[0]), 1e-4);
+        let mut problem = Problem {
+            starts: vec![Vector::new([0.5, 0.5])],
+            goal: Vector::new([0.1, 0.9]),
+            space_sampler: Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            valid: &wall,
+            radius: 0.05,
+        };
+        let mut rrt = Rrt::new(
+            Vector::new([0.1, 0.1]),
+            KdTreeMap::new(SquaredEuclidean),
+            &wall,
+        );
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let res = rrt.solve(&problem, &mut Solved::new(), &mut rng);
+        assert_eq!(res.status, PlanStatus::InvalidStart);
+        assert_eq!(res.path, None);
+        assert_eq!(res.stats.samples, 0);
+        assert_eq!(res.stats.validity_checks, 1);
+
+        problem.starts = vec![Vector::new([0.1, 0.1])];
+        problem.goal = Vector::new([0.5, 0.9]);
+        let res = rrt.solve(&problem, &mut Solved::new(), &mut rng);
+        assert_eq!(res.status, PlanStatus::InvalidGoal);
+        assert_eq!(res.path, None);
+        assert_eq!(res.stats.validity_checks, 2);
+
+        problem.goal = Vector::new([0.1, 0.9]);
+        let res = rrt.solve(&problem, &mut Solved::new(), &mut rng);
+        assert!(res.is_exact());
+        let path = res.path.expect("exact solution must have a path");
+        assert_eq!(path.first(), Some(&problem.starts[0]));
+        assert_eq!(path.last(), Some(&problem.goal));
+        assert_eq!(res.distance_to_goal, Some(0.0));
+        assert_eq!(res.stats.nodes, rrt.num_nodes() - 1);
+        assert!(res.stats.samples >= res.stats.nodes);
+        // the start and the goal are each checked once, then one transition per sample
+        assert_eq!(res.stats.validity_checks, res.stats.samples + 2);
+        #[cfg(feature = "std")]
+        assert!(res.stats.wall_time.is_some());
+
+        // a goal region can be reached even though its center is invalid
+        let ball = Problem {
+            starts: problem.starts.clone(),
+            goal: Tolerance {
+                center: Vector::new([0.5, 0.9]),
+                metric: Euclidean,
+                tolerance: 0.1,
+            },
+            space_sampler: problem.space_sampler,
+            valid: &wall,
+            radius: 0.05,
+        };
+        let res = rrt.solve(&ball, &mut Solved::new(), &mut rng);
+        assert!(res.is_exact());
+        let path = res.path.expect("exact solution must have a path");
+        assert!(ball.goal.is_satisfied(path.last().unwrap()));
+    }
 }
@@ -2,8 +2,21 @@
 
 use alloc::vec::Vec;
 
+use num_traits::Zero;
+use rand::Rng;
+
 use crate::{
-    nn::NearestNeighborsMap, sample::Sample, space::Interpolate, time::Timeout, valid::GeoValidate,
+    goal::Goal,
+    nn::{DynamicNearestNeighborsMap, NearestNeighborsMap},
+    sample::Sample,
+    space::Interpolate,
+    time::Timeout,
+    valid::{GeoValidate, Validate},
+};
+
+use super::{
+    problem::{sample_valid_goal, CountChecks, GoalSample, Recorder},
+    Graph, PlanResult, PlanStatus, Planner, Problem, Tree,
 };
 
 mod private {
@@ -15,6 +28,11 @@ use private::Node;
 #[derive(Clone, Debug)]
 /// A planner that combines two [`Rrt`]s growing toward each other.
 ///
+/// When used as a [`Planner`], both trees are cleared: the start tree is rooted at each valid start
+/// of the problem, and the goal tree at a configuration sampled from the goal.
+/// Since the goal tree must have a root, a goal which cannot be sampled is reported as
+/// [`PlanStatus::UnsupportedGoal`].
+///
 /// # Citation
 ///
 /// ```bibtex
@@ -38,10 +56,57 @@ pub struct RrtConnect<'a, C, NN, V> {
 #[derive(Clone, Debug)]
 struct HalfTree<C, NN> {
     configurations: Vec<C>,
+    /// The parent of each node; the parent of each root is `usize::MAX`.
     parents: Vec<usize>,
     nn: NN,
 }
 
+impl<C, NN> HalfTree<C, NN> {
+    /// Construct a tree with a root at each of `roots`, using `nn` as its nearest-neighbor
+    /// structure.
+    fn new(mut nn: NN, roots: Vec<C>) -> Self
+    where
+        NN: NearestNeighborsMap<C, Node>,
+        C: Clone,
+    {
+        for (i, root) in roots.iter().enumerate() {
+            nn.insert(root.clone(), Node(i));
+        }
+        Self {
+            parents: vec![usize::MAX; roots.len()],
+            configurations: roots,
+            nn,
+        }
+    }
+
+    /// Discard every node of this tree, then root it at each of `roots`.
+    fn reset(&mut self, roots: Vec<C>)
+    where
+        NN: DynamicNearestNeighborsMap<C, Node>,
+        C: Clone,
+    {
+        self.nn.clear();
+        for (i, root) in roots.iter().enumerate() {
+            self.nn.insert(root.clone(), Node(i));
+        }
+        self.parents = vec![usize::MAX; roots.len()];
+        self.configurations = roots;
+    }
+
+    /// Extract the path from the node with ID `id` to the root of this tree.
+    fn path_from(&self, mut id: usize) -> Vec<C>
+    where
+        C: Clone,
+    {
+        let mut traj = vec![self.configurations[id].clone()];
+        while self.parents[id] != usize::MAX {
+            id = self.parents[id];
+            traj.push(self.configurations[id].clone());
+        }
+        traj
+    }
+}
+
 #[expect(clippy::too_many_arguments)]
 /// Plan between two configurations using an [`RrtConnect`].
 ///
@@ -50,16 +115,17 @@ struct HalfTree<C, NN> {
 /// - `start`: The start configuration.
 /// - `valid`: The state validator.
 /// - `space_sampler`: A sampler for states in the configuration space.
-/// - `goal`: The goal state or sampler for goal states.
+/// - `goal`: The goal. The goal tree is rooted at a configuration sampled from it, so planning
+///   fails if it cannot be sampled.
 /// - `radius`: The radius by which to expand the RRT.
 /// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
 ///   over.
 /// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
 ///   returns `true`, the RRT grows toward the goal instead of to fill the space.
 /// - `rng`: The source of randomness.
-pub fn rrt_connect<C, NN, V, SP, TC, R, RNG>(
+pub fn rrt_connect<C, NN, V, SP, G, TC, R, RNG>(
     start: C,
-    goal: C,
+    goal: &G,
     nn: NN,
     valid: &V,
     space_sampler: &SP,
@@ -71,35 +137,34 @@ where
     NN: NearestNeighborsMap<C, Node> + Clone,
     V: GeoValidate<C>,
     SP: Sample<C, RNG>,
+    G: Goal<C>,
     R: Clone,
     C: Clone + Interpolate<Distance = R>,
     TC: Timeout,
+    RNG: Rng,
 {
-    let mut trees = RrtConnect::new(nn, start, goal, valid);
+    let mut trees = RrtConnect::new(nn, start, goal, valid, rng);
     trees.grow(space_sampler, radius, timeout, rng)
 }
 
 impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
-    pub fn new(mut nn: NN, start: C, goal: C, valid: &'a V) -> Self
+    /// Construct a new RRT-connect whose start tree is rooted at `start` and whose goal tree is
+    /// rooted at a configuration sampled from `goal` using `rng`, using `nn` as the
+    /// nearest-neighbor structure of each tree and `valid` as its state validator.
+    ///
+    /// If `goal` cannot be sampled, the goal tree is empty and [`RrtConnect::grow`] will never
+    /// find a path.
+    pub fn new<G, RNG>(nn: NN, start: C, goal: &G, valid: &'a V, rng: &mut RNG) -> Self
     where
         NN: Clone + NearestNeighborsMap<C, Node>,
         C: Clone,
+        G: Goal<C>,
+        RNG: Rng,
     {
-        let mut nn1 = nn.clone();
-        nn.insert(start.clone(), Node(0));
-        nn1.insert(goal.clone(), Node(0));
         Self {
             trees: [
-                HalfTree {
-                    configurations: vec![start],
-                    parents: vec![0],
-                    nn,
-                },
-                HalfTree {
-                    configurations: vec![goal],
-                    parents: vec![0],
-                    nn: nn1,
-                },
+                HalfTree::new(nn.clone(), vec![start]),
+                HalfTree::new(nn, goal.sample_goal(rng).into_iter().collect()),
             ],
             cross_edges: Vec::new(),
             valid,
@@ -107,7 +172,6 @@ impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
         }
     }
 
-    #[expect(clippy::missing_panics_doc)]
     pub fn grow<SP, TC, R, RNG>(
         &mut self,
         space_sampler: &SP,
@@ -123,12 +187,33 @@ impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
         R: Clone,
         C: Clone + Interpolate<Distance = R>,
     {
-        if !self
-            .trees
-            .iter()
-            .all(|t| self.valid.is_valid_configuration(&t.configurations[0]))
-        {
-            // invalid start/goal
+        self.grow_help(self.valid, space_sampler, radius, timeout, rng)
+    }
+
+    /// Grow both trees toward each other, checking validity with `valid`, and return the path
+    /// through the first connection between them.
+    fn grow_help<W, SP, TC, R, RNG>(
+        &mut self,
+        valid: &W,
+        space_sampler: &SP,
+        radius: R,
+        timeout: &mut TC,
+        rng: &mut RNG,
+    ) -> Option<Vec<C>>
+    where
+        W: GeoValidate<C>,
+        SP: Sample<C, RNG>,
+        TC: Timeout,
+        NN: NearestNeighborsMap<C, Node>,
+        R: Clone,
+        C: Clone + Interpolate<Distance = R>,
+    {
+        if !self.trees.iter().all(|t| {
+            t.configurations
+                .first()
+                .is_some_and(|c| valid.is_valid_configuration(c))
+        }) {
+            // missing or invalid start/goal
             return None;
         }
 
@@ -144,7 +229,7 @@ impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
                 Ok(c) | Err(c) => c,
             };
 
-            if !self.valid.is_valid_transition(start_cfg, &end_cfg) {
+            if !valid.is_valid_transition(start_cfg, &end_cfg) {
                 continue;
             }
             timeout.update_node_count(1);
@@ -166,7 +251,7 @@ impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
                     Err(c) => (c, true),
                 };
 
-                if !self.valid.is_valid_transition(start_cfg, &end_cfg) {
+                if !valid.is_valid_transition(start_cfg, &end_cfg) {
                     break;
                 }
 
@@ -179,27 +264,17 @@ impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
                         (start_id, new_id)
                     });
 
-                    let mut traj = Vec::new();
-                    let (mut p0, mut p1) = self.cross_edges.last().unwrap();
+                    let &(p0, p1) = self.cross_edges.last().unwrap();
 
-                    // extract first half of path
-                    while p0 != 0 {
-                        traj.push(self.trees[0].configurations[p0].clone());
-                        p0 = self.trees[0].parents[p0];
-                    }
-                    traj.push(self.trees[0].configurations[0].clone());
+                    // extract first half of path, then second half
+                    let mut traj = self.trees[0].path_from(p0);
                     traj.reverse();
-
-                    // extract second half of path
-                    while p1 != 0 {
-                        traj.push(self.trees[1].configurations[p1].clone());
-                        p1 = self.trees[1].parents[p1];
-                    }
-                    traj.push(self.trees[1].configurations[0].clone());
+                    traj.extend(self.trees[1].path_from(p1));
 
                     res = Some(traj);
                     break;
                 }
+                timeout.update_node_count(1);
                 let ext_id = tb.configurations.len();
 
                 tb.configurations.push(end_cfg.clone());
@@ -213,32 +288,158 @@ impl<'a, C, NN, V> RrtConnect<'a, C, NN, V> {
         res
     }
 
+    /// Discard both trees, then root the start tree at each of `starts` and the goal tree at
+    /// `goal`.
+    fn reset(&mut self, starts: Vec<C>, goal: C)
+    where
+        NN: DynamicNearestNeighborsMap<C, Node>,
+        C: Clone,
+    {
+        self.trees[0].reset(starts);
+        self.trees[1].reset(vec![goal]);
+        self.cross_edges.clear();
+        self.next = 0;
+    }
+
     pub fn num_nodes(&self) -> usize {
         self.trees.iter().map(|t| t.configurations.len()).sum()
     }
+
+    #[must_use]
+    /// Get a read-only view of the tree grown from the start.
+    pub fn start_tree(&self) -> Tree<'_, C> {
+        Tree::new(&self.trees[0].configurations, &self.trees[0].parents)
+    }
+
+    #[must_use]
+    /// Get a read-only view of the tree grown from the goal.
+    pub fn goal_tree(&self) -> Tree<'_, C> {
+        Tree::new(&self.trees[1].configurations, &self.trees[1].parents)
+    }
+
+    /// Iterate over the edges which connected the two trees, as pairs of node IDs
+    /// `(start tree node, goal tree node)`.
+    /// There is one such edge for each solution found.
+    pub fn cross_edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cross_edges.iter().copied()
+    }
+
+    #[must_use]
+    /// Get both trees as a single directed [`Graph`] for export.
+    ///
+    /// The nodes of the start tree come first, followed by the nodes of the goal tree, so the ID
+    /// of node `i` of the goal tree is offset by the number of nodes in the start tree.
+    /// Each node has an edge to each of its children, and each cross edge is directed from the
+    /// start tree to the goal tree.
+    pub fn graph(&self) -> Graph<'_, C> {
+        let offset = self.trees[0].configurations.len();
+        let (start, goal) = (self.start_tree(), self.goal_tree());
+        Graph::new(
+            self.trees.iter().flat_map(|t| &t.configurations).collect(),
+            start
+                .edges()
+                .chain(goal.edges().map(|(p, c)| (p + offset, c + offset)))
+                .chain(self.cross_edges().map(|(a, b)| (a, b + offset)))
+                .collect(),
+            true,
+        )
+    }
+}
+
+impl<'a, C, NN, V, SP, G, R, RNG> Planner<'a, C, V, SP, G, R, RNG> for RrtConnect<'a, C, NN, V>
+where
+    V: GeoValidate<C>,
+    SP: Sample<C, RNG>,
+    G: Goal<C>,
+    NN: DynamicNearestNeighborsMap<C, Node>,
+    R: Clone,
+    C: Clone + Interpolate<Distance = R>,
+    RNG: Rng,
+{
+    fn solve<TC: Timeout>(
+        &mut self,
+        problem: &Problem<'a, C, V, SP, G, R>,
+        timeout: &mut TC,
+        rng: &mut RNG,
+    ) -> PlanResult<C, G::Distance> {
+        self.valid = problem.valid;
+        let valid = CountChecks::new(problem.valid);
+        let mut timeout = Recorder::new(timeout);
+        let starts: Vec<C> = problem
+            .starts
+            .iter()
+            .filter(|c| valid.is_valid_configuration(c))
+            .cloned()
+            .collect();
+        if let Some(start) = starts.iter().find(|c| problem.goal.is_satisfied(c)) {
+            timeout.notify_solved();
+            return timeout.finish(
+                PlanStatus::Exact,
+                Some(vec![start.clone()]),
+                Some(G::Distance::zero()),
+                valid.count(),
+            );
+        }
+        if starts.is_empty() {
+            return timeout.finish(PlanStatus::InvalidStart, None, None, valid.count());
+        }
+        // the goal tree needs a valid root, so keep sampling the goal until one is found
+        let goal = loop {
+            let status = match sample_valid_goal(&problem.goal, &valid, rng) {
+                GoalSample::Valid(goal) => break goal,
+                GoalSample::Missed if !timeout.is_over() => continue,
+                GoalSample::Missed => PlanStatus::Timeout,
+                GoalSample::Invalid => PlanStatus::InvalidGoal,
+                GoalSample::Unsupported => PlanStatus::UnsupportedGoal,
+            };
+            return timeout.finish(status, None, None, valid.count());
+        };
+        self.reset(starts, goal);
+
+        let path = self.grow_help(
+            &valid,
+            &problem.space_sampler,
+            problem.radius.clone(),
+            &mut timeout,
+            rng,
+        );
+        let checks = valid.count();
+        match path {
+            Some(path) => timeout.finish(
+                PlanStatus::Exact,
+                Some(path),
+                Some(G::Distance::zero()),
+                checks,
+            ),
+            None => timeout.finish(PlanStatus::Timeout, None, None, checks),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        metric::{Metric, SquaredEuclidean},
+        geo::Problem,
+        metric::{Euclidean, Metric, SquaredEuclidean, WeightedSum},
         nn::KdTreeMap,
-        sample::Rectangle,
-        space::Vector,
+        sample::{Everywhere, Rectangle},
+        space::{Angle, Orient, Pose3d, PoseRadius, Vector, WeightedPoseDistance},
         time::Solved,
-        valid::AlwaysValid,
+        valid::{AlwaysValid, SampleInterpolate},
     };
     use rand::SeedableRng;
     use rand_chacha::ChaCha20Rng;
 
     #[test]
     fn rrtc_plane2d() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
         let mut rrtc = RrtConnect::new(
             KdTreeMap::new(SquaredEuclidean),
             Vector::new([0.0, 0.0]),
-            Vector::new([1.0, 1.0]),
+            &Vector::new([1.0, 1.0]),
             &AlwaysValid,
+            &mut rng,
         );
         let radius = 0.05;
         let res = rrtc
@@ -249,7 +450,7 @@ mod tests {
                 },
                 radius,
                 &mut Solved::new(),
-                &mut ChaCha20Rng::seed_from_u64(2707),
+                &mut rng,
             )
             .unwrap();
 
@@ -263,5 +464,268 @@ mod tests {
                 .all(|a| SquaredEuclidean.distance(&a[0], &a[1]) <= radius),
             "all transitions must be within growth radius"
         );
+
+        // every node but the two roots has a parent, plus one edge connecting the trees
+        let graph = rrtc.graph();
+        assert_eq!(graph.nodes().len(), rrtc.num_nodes());
+        assert_eq!(graph.edges().len(), rrtc.num_nodes() - 1);
+        assert_eq!(rrtc.cross_edges().count(), 1);
+    }
+
+    #[test]
+    fn rrtc_pose3d() {
+        let start = Pose3d {
+            position: Vector::new([0.0; 3]),
+            orient: Orient::identity(),
+        };
+        let goal = Pose3d {
+            position: Vector::new([1.0; 3]),
+            orient: Orient::from_euler(1.0, 2.0, 3.0),
+        };
+        let radius = PoseRadius {
+            angle_dist: 0.1,
+            position_dist: 0.05,
+        };
+        let res = rrt_connect(
+            start,
+            &goal,
+            KdTreeMap::new(WeightedPoseDistance {
+                position_metric: SquaredEuclidean,
+                position_weight: 1.0,
+                angle_metric: SquaredEuclidean,
+                angle_weight: 1.0,
+            }),
+            &AlwaysValid,
+            &Rectangle {
+                min: Vector::new([0.0; 3]),
+                max: Vector::new([1.0; 3]),
+            },
+            radius,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        )
+        .unwrap();
+
+        assert_eq!(res.first(), Some(&start));
+        assert_eq!(res.last(), Some(&goal));
+        assert!(
+            res.windows(2)
+                .all(|a| a[0].orient.angle_to(a[1].orient) <= radius.angle_dist + 1e-9),
+            "all rotations must be within growth radius"
+        );
+    }
+
+    #[test]
+    fn rrtc_product() {
+        let start = (
+            Vector::new([0.0; 2]),
+            Angle::new(0.0),
+            Vector::new([0.0; 7]),
+        );
+        let goal = (
+            Vector::new([1.0; 2]),
+            Angle::new(3.0),
+            Vector::new([0.5; 7]),
+        );
+        let radius = (0.05, 0.1, 0.05);
+        let res = rrt_connect(
+            start,
+            &goal,
+            KdTreeMap::new(WeightedSum {
+                metrics: (SquaredEuclidean, Euclidean, SquaredEuclidean),
+                weights: [1.0; 3],
+            }),
+            &AlwaysValid,
+            &(
+                Rectangle {
+                    min: Vector::new([0.0; 2]),
+                    max: Vector::new([1.0; 2]),
+                },
+                Everywhere,
+                Rectangle {
+                    min: Vector::new([0.0; 7]),
+                    max: Vector::new([1.0; 7]),
+                },
+            ),
+            radius,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        )
+        .unwrap();
+
+        assert_eq!(res.first(), Some(&start));
+        assert_eq!(res.last(), Some(&goal));
+        assert!(
+            res.windows(2)
+                .all(|a| SquaredEuclidean.distance(&a[0].0, &a[1].0) <= radius.0
+                    && Euclidean.distance(&a[0].1, &a[1].1) <= radius.1 + 1e-9),
+            "all transitions must be within growth radius"
+        );
+    }
+
+    #[test]
+    fn rrtc_region() {
+        // a box in the corner of the space
+        let goal = Rectangle {
+            min: Vector::new([0.8, 0.8]),
+            max: Vector::new([1.0, 1.0]),
+        };
+        let res = rrt_connect(
+            Vector::new([0.0, 0.0]),
+            &goal,
+            KdTreeMap::new(SquaredEuclidean),
+            &AlwaysValid,
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            0.05,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        )
+        .unwrap();
+        assert_eq!(res.first(), Some(&Vector::new([0.0, 0.0])));
+        assert!(goal.is_satisfied(res.last().unwrap()));
+
+        // a goal which cannot be sampled can never root the goal tree
+        let res = rrt_connect(
+            Vector::new([0.0, 0.0]),
+            &|c: &Vector<2>| c[0] > 0.8,
+            KdTreeMap::new(SquaredEuclidean),
+            &AlwaysValid,
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            0.05,
+            &mut Solved::new(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        assert_eq!(res, None);
+    }
+
+    #[test]
+    fn reroot() {
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| !(0.45..0.55).contains(&c[0]) || c[1] > 0.8,
+            1e-4,
+        );
+        let problem = Problem {
+            starts: vec![Vector::new([0.1, 0.5])],
+            goal: Vector::new([0.9, 0.5]),
+            space_sampler: Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            valid: &wall,
+            radius: 0.05,
+        };
+
+        // the constructor's start and goal are invalid, and must not be used by the solution
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let mut rrtc = RrtConnect::new(
+            KdTreeMap::new(SquaredEuclidean),
+            Vector::new([0.5, 0.5]),
+            &Vector::new([0.5, 0.2]),
+            &wall,
+            &mut rng,
+        );
+        let res = rrtc.solve(&problem, &mut Solved::new(), &mut rng);
+        assert_eq!(res.status, PlanStatus::Exact);
+        let path = res.path.expect("exact solution must have a path");
+        assert_eq!(path.first(), Some(&problem.starts[0]));
+        assert_eq!(path.last(), Some(&problem.goal));
+        assert!(path
+            .windows(2)
+            .all(|a| wall.is_valid_transition(&a[0], &a[1])));
+        assert_eq!(rrtc.start_tree().roots().count(), 1);
+        assert_eq!(rrtc.goal_tree().roots().count(), 1);
+    }
+
+    #[test]
+    fn plan_result() {
+        // a wall at x = 0.5
+        let wall = SampleInterpolate::new(|c: &Vector<2>| !(0.45..0.55).contains(&c[0]), 1e-4);
+        let mut problem = Problem {
+            starts: vec![Vector::new([0.5, 0.5])],
+            goal: Vector::new([0.1, 0.9]),
+            space_sampler: Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            valid: &wall,
+            radius: 0.05,
+        };
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let mut rrtc = RrtConnect::new(
+            KdTreeMap::new(SquaredEuclidean),
+            Vector::new([0.1, 0.1]),
+            &problem.goal,
+            &wall,
+            &mut rng,
+        );
+
+        let res = rrtc.solve(&problem, &mut Solved::new(), &mut rng);
+        assert_eq!(res.status, PlanStatus::InvalidStart);
+        assert_eq!(res.path, None);
+        assert_eq!(res.stats.samples, 0);
+        assert_eq!(res.stats.validity_checks, 1);
+
+        problem.starts = vec![Vector::new([0.1, 0.1])];
+        problem.goal = Vector::new([0.5, 0.9]);
+        let res = rrtc.solve(&problem, &mut Solved::new(), &mut rng);
+        assert_eq!(res.status, PlanStatus::InvalidGoal);
+        assert_eq!(res.path, None);
+        assert_eq!(res.stats.validity_checks, 2);
+
+        // a start which already satisfies the goal is a solution on its own
+        problem.goal = Vector::new([0.1, 0.1]);
+        let res = rrtc.solve(&problem, &mut Solved::new(), &mut rng);
+        assert!(res.is_exact());
+        assert_eq!(res.path, Some(vec![problem.starts[0]]));
+        assert_eq!(res.stats.samples, 0);
+
+        problem.goal = Vector::new([0.1, 0.9]);
+        let res = rrtc.solve(&problem, &mut Solved::new(), &mut rng);
+        assert!(res.is_exact());
+        let path = res.path.expect("exact solution must have a path");
+        assert_eq!(path.first(), Some(&problem.starts[0]));
+        assert_eq!(path.last(), Some(&problem.goal));
+        assert_eq!(res.distance_to_goal, Some(0.0));
+        assert_eq!(res.stats.nodes, rrtc.num_nodes() - 2);
+        assert!(res.stats.samples > 0);
+        // the start and goal are each checked twice, then at least one transition per sample
+        assert!(res.stats.validity_checks >= res.stats.samples + 4);
+        #[cfg(feature = "std")]
+        assert!(res.stats.wall_time.is_some());
+
+        // a goal region which is mostly blocked by the wall still has valid configurations
+        let region = Problem {
+            starts: problem.starts.clone(),
+            goal: Rectangle {
+                min: Vector::new([0.445, 0.8]),
+                max: Vector::new([0.55, 0.9]),
+            },
+            space_sampler: problem.space_sampler,
+            valid: &wall,
+            radius: 0.05,
+        };
+        let res = rrtc.solve(&region, &mut Solved::new(), &mut rng);
+        assert!(res.is_exact());
+        let path = res.path.expect("exact solution must have a path");
+        assert!(region.goal.is_satisfied(path.last().unwrap()));
+
+        // a goal which cannot be sampled can never root the goal tree
+        let predicate = Problem {
+            starts: problem.starts.clone(),
+            goal: |c: &Vector<2>| c[1] > 0.8,
+            space_sampler: problem.space_sampler,
+            valid: &wall,
+            radius: 0.05,
+        };
+        let res = rrtc.solve(&predicate, &mut Solved::new(), &mut rng);
+        assert_eq!(res.status, PlanStatus::UnsupportedGoal);
+        assert_eq!(res.path, None);
     }
 }
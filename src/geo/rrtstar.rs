@@ -0,0 +1,699 @@
+//! RRT*.
+
+use alloc::vec::Vec;
+use core::{cmp::Ordering, ops::Add};
+
+use num_traits::Zero;
+use rand::Rng;
+
+use crate::{
+    goal::Goal,
+    metric::Metric,
+    nn::NearestNeighborsMap,
+    sample::{InformedSample, Sample},
+    space::Interpolate,
+    time::Timeout,
+    valid::{GeoValidate, Validate},
+};
+
+use super::Neighbors;
+
+/// An optimal rapidly-exploring random tree: an asymptotically-optimal, anytime variant of
+/// [`Rrt`](super::Rrt).
+///
+/// Each new node is connected to the lowest-cost parent in its neighborhood, and then neighboring
+/// nodes are rewired through the new node if doing so reduces their cost.
+///
+/// # Generic parameters
+///
+/// - `C` should be the configuration of a robot.
+/// - `NN` should be the nearest neighbors data structure, which can use `C` as a key and implement
+///   whatever queries the chosen [`Neighbors`] strategy needs: range queries
+///   ([`RangeNearestNeighborsMap`](crate::nn::RangeNearestNeighborsMap)) for a
+///   [`RadiusNeighborhood`](super::RadiusNeighborhood), and additionally k-nearest queries
+///   ([`KNearestNeighborsMap`](crate::nn::KNearestNeighborsMap)) for a
+///   [`Neighborhood`](super::Neighborhood).
+/// - `V` should be a state validator; it must implement [`GeoValidate`] for `C`.
+/// - `M` is the metric used to compute the cost of paths. It must implement [`Metric`] for `C`.
+/// - `D` is the cost of a path, and the distance type of `M`.
+///
+/// # Citation
+///
+/// ```bibtex
+/// @article{karaman2011sampling,
+///   title={Sampling-based algorithms for optimal motion planning},
+///   author={Karaman, Sertac and Frazzoli, Emilio},
+///   journal={The International Journal of Robotics Research},
+///   volume={30},
+///   number={7},
+///   pages={846--894},
+///   year={2011},
+///   publisher={SAGE Publications}
+/// }
+/// ```
+pub struct RrtStar<'a, C, NN, V, M, D> {
+    /// buffer of saved configurations
+    /// configurations[0] is the root
+    configurations: Vec<C>,
+    /// ids for each configuration
+    /// `parent_ids[0]` is ignorable
+    parent_ids: Vec<usize>,
+    /// The children of each node, used to propagate cost updates when rewiring.
+    children: Vec<Vec<usize>>,
+    /// The cost of the path from the root to each node.
+    costs: Vec<D>,
+    /// Whether each node satisfies the goal.
+    is_goal: Vec<bool>,
+    /// The IDs of all nodes which satisfy the goal.
+    goal_ids: Vec<usize>,
+    /// The nearest neighbors lookup.
+    nn: NN,
+    /// The state validator.
+    valid: &'a V,
+    /// The cost metric.
+    metric: M,
+}
+
+/// Workaround module to avoid exposing implementation details of `Node` to consumers.
+mod private {
+    pub struct Node(pub usize);
+}
+use private::Node;
+
+#[expect(clippy::too_many_arguments)]
+/// Plan between two configurations using an [`RrtStar`], returning the best path found before
+/// `timeout` is over.
+///
+/// # Parameters
+///
+/// - `start`: The start configuration.
+/// - `nn`: The nearest-neighbor structure.
+/// - `valid`: The state validator.
+/// - `metric`: The metric for the cost of a path.
+/// - `space_sampler`: A sampler for states in the configuration space.
+/// - `goal`: The goal. Every node which satisfies it is a candidate endpoint of the best path.
+/// - `radius`: The radius by which to expand the RRT.
+/// - `neighborhood`: The neighborhood of each new node to search for parents and rewire.
+/// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
+///   over.
+/// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
+///   returns `true`, the RRT grows toward a sample from the goal instead of to fill the space.
+/// - `rng`: The source of randomness.
+pub fn rrt_star<C, NN, NB, V, M, D, SP, G, TC, TG, R, RNG>(
+    start: C,
+    nn: NN,
+    valid: &V,
+    metric: M,
+    space_sampler: &SP,
+    goal: &G,
+    radius: R,
+    neighborhood: &NB,
+    timeout: &mut TC,
+    target_goal_distn: &TG,
+    rng: &mut RNG,
+) -> Option<Vec<C>>
+where
+    NN: NearestNeighborsMap<C, Node>,
+    NB: Neighbors<C, Node, NN>,
+    V: GeoValidate<C>,
+    M: Metric<C, Distance = D>,
+    D: Clone + PartialOrd + Add<Output = D> + Zero,
+    SP: Sample<C, RNG>,
+    G: Goal<C>,
+    R: Clone,
+    C: Clone + Interpolate<Distance = R>,
+    TC: Timeout,
+    TG: Sample<bool, RNG>,
+    RNG: Rng,
+{
+    let mut rrt = RrtStar::new(start, nn, valid, metric);
+    rrt.grow_toward(
+        space_sampler,
+        goal,
+        radius,
+        neighborhood,
+        timeout,
+        target_goal_distn,
+        rng,
+    )
+}
+
+#[expect(clippy::too_many_arguments)]
+/// Plan between two configurations using an Informed RRT*, returning the best path found before
+/// `timeout` is over.
+///
+/// Informed RRT* is an [`RrtStar`] which, once it has found a solution, only samples the
+/// configurations which could possibly improve upon that solution.
+/// `sampler` is responsible for restricting its samples accordingly; for problems with a single
+/// start and goal in a Euclidean space, use an [`InformedSet`](crate::sample::InformedSet).
+///
+/// # Parameters
+///
+/// - `start`: The start configuration.
+/// - `nn`: The nearest-neighbor structure.
+/// - `valid`: The state validator.
+/// - `metric`: The metric for the cost of a path.
+/// - `sampler`: An informed sampler for states in the configuration space.
+/// - `goal`: The goal. Every node which satisfies it is a candidate endpoint of the best path.
+/// - `radius`: The radius by which to expand the RRT.
+/// - `neighborhood`: The neighborhood of each new node to search for parents and rewire.
+/// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
+///   over.
+/// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
+///   returns `true`, the RRT grows toward a sample from the goal instead of to fill the space.
+/// - `rng`: The source of randomness.
+///
+/// # Citation
+///
+/// ```bibtex
+/// @inproceedings{gammell2014informed,
+///   title={Informed RRT*: Optimal sampling-based path planning focused via direct sampling of an
+///          admissible ellipsoidal heuristic},
+///   author={Gammell, Jonathan D and Srinivasa, Siddhartha S and Barfoot, Timothy D},
+///   booktitle={2014 IEEE/RSJ International Conference on Intelligent Robots and Systems},
+///   pages={2997--3004},
+///   year={2014},
+///   organization={IEEE}
+/// }
+/// ```
+pub fn informed_rrt_star<C, NN, NB, V, M, D, S, G, TC, TG, R, RNG>(
+    start: C,
+    nn: NN,
+    valid: &V,
+    metric: M,
+    sampler: &mut S,
+    goal: &G,
+    radius: R,
+    neighborhood: &NB,
+    timeout: &mut TC,
+    target_goal_distn: &TG,
+    rng: &mut RNG,
+) -> Option<Vec<C>>
+where
+    NN: NearestNeighborsMap<C, Node>,
+    NB: Neighbors<C, Node, NN>,
+    V: GeoValidate<C>,
+    M: Metric<C, Distance = D>,
+    D: Clone + PartialOrd + Add<Output = D> + Zero,
+    S: InformedSample<C, D, RNG>,
+    G: Goal<C>,
+    R: Clone,
+    C: Clone + Interpolate<Distance = R>,
+    TC: Timeout,
+    TG: Sample<bool, RNG>,
+    RNG: Rng,
+{
+    let mut rrt = RrtStar::new(start, nn, valid, metric);
+    rrt.grow_informed(
+        sampler,
+        goal,
+        radius,
+        neighborhood,
+        timeout,
+        target_goal_distn,
+        rng,
+    )
+}
+
+impl<'a, C, NN, V, M, D> RrtStar<'a, C, NN, V, M, D> {
+    /// Construct a new RRT* rooted at `root`, using `nn` as its nearest-neighbor structure, `valid`
+    /// as its state validator, and `metric` to measure the cost of paths.
+    pub fn new(root: C, mut nn: NN, valid: &'a V, metric: M) -> Self
+    where
+        NN: NearestNeighborsMap<C, Node>,
+        C: Clone,
+        V: Validate<C>,
+        M: Metric<C, Distance = D>,
+        D: Zero,
+    {
+        nn.insert(root.clone(), Node(0));
+        Self {
+            configurations: vec![root],
+            parent_ids: vec![usize::MAX],
+            children: vec![Vec::new()],
+            costs: vec![D::zero()],
+            is_goal: vec![false],
+            goal_ids: Vec::new(),
+            nn,
+            valid,
+            metric,
+        }
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    /// Grow this RRT* toward the provided goal `goal` until `timeout` is over, returning the best
+    /// path found so far (if any).
+    ///
+    /// This may be called repeatedly to continue refining the tree.
+    ///
+    /// # Parameters
+    ///
+    /// - `space_sampler`: A sampler for states in the configuration space.
+    /// - `goal`: The goal. Every node which satisfies it is a candidate endpoint of the best path.
+    /// - `radius`: The radius by which to expand the RRT.
+    /// - `neighborhood`: The neighborhood of each new node to search for parents and rewire.
+    /// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
+    ///   over.
+    /// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
+    ///   returns `true`, the RRT grows toward a sample from the goal instead of to fill the space.
+    /// - `rng`: The source of randomness.
+    pub fn grow_toward<NB, SP, G, TC, TG, R, RNG>(
+        &mut self,
+        space_sampler: &SP,
+        goal: &G,
+        radius: R,
+        neighborhood: &NB,
+        timeout: &mut TC,
+        target_goal_distn: &TG,
+        rng: &mut RNG,
+    ) -> Option<Vec<C>>
+    where
+        V: GeoValidate<C>,
+        SP: Sample<C, RNG>,
+        G: Goal<C>,
+        TG: Sample<bool, RNG>,
+        RNG: Rng,
+        TC: Timeout,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        M: Metric<C, Distance = D>,
+        D: Clone + PartialOrd + Add<Output = D>,
+        R: Clone,
+        C: Clone + Interpolate<Distance = R>,
+    {
+        if !self.valid.is_valid_configuration(&self.configurations[0]) {
+            return None; // invalid configuration
+        }
+        self.mark_root(goal, timeout);
+        while !timeout.is_over() {
+            timeout.update_sample_count(1);
+            let goal_sample = if target_goal_distn.sample(rng) {
+                goal.sample_goal(rng)
+            } else {
+                None
+            };
+            let sample_goal = goal_sample.is_some();
+            let target = goal_sample.unwrap_or_else(|| space_sampler.sample(rng));
+            self.extend(
+                &target,
+                sample_goal,
+                goal,
+                radius.clone(),
+                neighborhood,
+                timeout,
+            );
+        }
+
+        self.best_path()
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    /// Grow this RRT* toward the provided goal `goal` until `timeout` is over, focusing samples on
+    /// configurations which could improve on the best path, and returning the best path found so
+    /// far (if any).
+    ///
+    /// Whenever a solution is known, its cost is given to `sampler` before drawing each sample.
+    /// This is the sampling strategy of Informed RRT*; see [`informed_rrt_star`].
+    ///
+    /// # Parameters
+    ///
+    /// - `sampler`: An informed sampler for states in the configuration space.
+    /// - `goal`: The goal. Every node which satisfies it is a candidate endpoint of the best path.
+    /// - `radius`: The radius by which to expand the RRT.
+    /// - `neighborhood`: The neighborhood of each new node to search for parents and rewire.
+    /// - `timeout`: The timeout condition. The planning algorithm will continue until `timeout` is
+    ///   over.
+    /// - `target_goal_distn`: A sampler which returns `true` with some probability; every time it
+    ///   returns `true`, the RRT grows toward a sample from the goal instead of to fill the space.
+    /// - `rng`: The source of randomness.
+    pub fn grow_informed<NB, S, G, TC, TG, R, RNG>(
+        &mut self,
+        sampler: &mut S,
+        goal: &G,
+        radius: R,
+        neighborhood: &NB,
+        timeout: &mut TC,
+        target_goal_distn: &TG,
+        rng: &mut RNG,
+    ) -> Option<Vec<C>>
+    where
+        V: GeoValidate<C>,
+        S: InformedSample<C, D, RNG>,
+        G: Goal<C>,
+        TG: Sample<bool, RNG>,
+        RNG: Rng,
+        TC: Timeout,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        M: Metric<C, Distance = D>,
+        D: Clone + PartialOrd + Add<Output = D>,
+        R: Clone,
+        C: Clone + Interpolate<Distance = R>,
+    {
+        if !self.valid.is_valid_configuration(&self.configurations[0]) {
+            return None; // invalid configuration
+        }
+        self.mark_root(goal, timeout);
+        while !timeout.is_over() {
+            if let Some(cost) = self.best_cost() {
+                sampler.set_best_cost(cost.clone());
+            }
+            timeout.update_sample_count(1);
+            let goal_sample = if target_goal_distn.sample(rng) {
+                goal.sample_goal(rng)
+            } else {
+                None
+            };
+            let sample_goal = goal_sample.is_some();
+            let target = goal_sample.unwrap_or_else(|| sampler.sample(rng));
+            self.extend(
+                &target,
+                sample_goal,
+                goal,
+                radius.clone(),
+                neighborhood,
+                timeout,
+            );
+        }
+
+        self.best_path()
+    }
+
+    /// Record whether the root satisfies `goal`.
+    /// The root is created before the goal is known, so it must be checked once growth begins.
+    fn mark_root<G, TC>(&mut self, goal: &G, timeout: &mut TC)
+    where
+        G: Goal<C>,
+        TC: Timeout,
+    {
+        if !self.is_goal[0] && goal.is_satisfied(&self.configurations[0]) {
+            self.is_goal[0] = true;
+            self.goal_ids.push(0);
+            timeout.notify_solved();
+        }
+    }
+
+    /// Attempt to extend the tree toward `target`, choosing the best parent for the new node and
+    /// rewiring its neighborhood.
+    /// `sample_goal` should be `true` if `target` was sampled from `goal`.
+    fn extend<NB, G, TC, R>(
+        &mut self,
+        target: &C,
+        sample_goal: bool,
+        goal: &G,
+        radius: R,
+        neighborhood: &NB,
+        timeout: &mut TC,
+    ) where
+        V: GeoValidate<C>,
+        G: Goal<C>,
+        TC: Timeout,
+        NN: NearestNeighborsMap<C, Node>,
+        NB: Neighbors<C, Node, NN>,
+        M: Metric<C, Distance = D>,
+        D: Clone + PartialOrd + Add<Output = D>,
+        C: Clone + Interpolate<Distance = R>,
+    {
+        let (nearest_cfg, &Node(nearest_id)) = self
+            .nn
+            .nearest(target)
+            .expect("NN must always have elements");
+        let (reached, new_cfg) = match nearest_cfg.interpolate(target, radius) {
+            Ok(c) => (false, c),
+            Err(c) => (true, c),
+        };
+        if sample_goal && reached && self.is_goal[nearest_id] {
+            // we already have a node at this goal
+            return;
+        }
+        let reached_goal = goal.is_satisfied(&new_cfg);
+        if !self.valid.is_valid_transition(nearest_cfg, &new_cfg) {
+            return;
+        }
+
+        let new_id = self.configurations.len();
+        let near = neighborhood
+            .neighbors(&self.nn, &new_cfg, new_id + 1)
+            .into_iter()
+            .map(|(_, &Node(n), _)| n)
+            .filter(|&n| n != nearest_id)
+            .collect::<Vec<_>>();
+
+        // choose parent: the lowest-cost neighbor with a valid transition to the new node
+        let nearest_cost = self.costs[nearest_id].clone()
+            + self
+                .metric
+                .distance(&self.configurations[nearest_id], &new_cfg);
+        let mut candidates = near
+            .iter()
+            .map(|&n| {
+                let cost =
+                    self.costs[n].clone() + self.metric.distance(&self.configurations[n], &new_cfg);
+                (cost, n)
+            })
+            .filter(|(cost, _)| cost < &nearest_cost)
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        let (cost, parent_id) = candidates
+            .into_iter()
+            .find(|&(_, n)| {
+                self.valid
+                    .is_valid_transition(&self.configurations[n], &new_cfg)
+            })
+            .unwrap_or((nearest_cost, nearest_id));
+
+        timeout.update_node_count(1);
+        self.configurations.push(new_cfg.clone());
+        self.parent_ids.push(parent_id);
+        self.children.push(Vec::new());
+        self.children[parent_id].push(new_id);
+        self.costs.push(cost.clone());
+        self.is_goal.push(reached_goal);
+        debug_assert_eq!(
+            self.configurations.len(),
+            self.parent_ids.len(),
+            "number of configurations and parents must be equal"
+        );
+        self.nn.insert(new_cfg.clone(), Node(new_id));
+        if reached_goal {
+            self.goal_ids.push(new_id);
+            timeout.notify_solved();
+        }
+
+        // rewire neighbors through the new node if it makes them cheaper
+        for n in near.into_iter().filter(|&n| n != parent_id) {
+            let new_cost = cost.clone() + self.metric.distance(&new_cfg, &self.configurations[n]);
+            if new_cost < self.costs[n]
+                && self
+                    .valid
+                    .is_valid_transition(&new_cfg, &self.configurations[n])
+            {
+                self.reparent(n, new_id, new_cost);
+            }
+        }
+    }
+
+    /// Make `parent_id` the parent of `id`, updating the cost of `id` and all its descendants.
+    fn reparent(&mut self, id: usize, parent_id: usize, cost: D)
+    where
+        M: Metric<C, Distance = D>,
+        D: Clone + PartialOrd + Add<Output = D>,
+    {
+        let old_parent = self.parent_ids[id];
+        self.children[old_parent].retain(|&c| c != id);
+        self.children[parent_id].push(id);
+        self.parent_ids[id] = parent_id;
+        self.costs[id] = cost;
+
+        let mut stack = self.children[id].clone();
+        while let Some(c) = stack.pop() {
+            let p = self.parent_ids[c];
+            self.costs[c] = self.costs[p].clone()
+                + self
+                    .metric
+                    .distance(&self.configurations[p], &self.configurations[c]);
+            stack.extend_from_slice(&self.children[c]);
+        }
+    }
+
+    /// Get the ID of the lowest-cost node which reached the goal.
+    fn best_goal(&self) -> Option<usize>
+    where
+        D: PartialOrd,
+    {
+        self.goal_ids.iter().copied().min_by(|&a, &b| {
+            self.costs[a]
+                .partial_cmp(&self.costs[b])
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+
+    /// Get the lowest-cost path to the goal found so far, or `None` if no path has been found.
+    pub fn best_path(&self) -> Option<Vec<C>>
+    where
+        C: Clone,
+        D: PartialOrd,
+    {
+        let mut id = self.best_goal()?;
+        let mut traj = Vec::new();
+        while id != 0 {
+            traj.push(self.configurations[id].clone());
+            id = self.parent_ids[id];
+        }
+        traj.push(self.configurations[0].clone());
+        traj.reverse();
+        Some(traj)
+    }
+
+    /// Get the cost of the lowest-cost path to the goal found so far, or `None` if no path has
+    /// been found.
+    pub fn best_cost(&self) -> Option<&D>
+    where
+        D: PartialOrd,
+    {
+        self.best_goal().map(|id| &self.costs[id])
+    }
+
+    /// Get the number of total nodes in this tree.
+    pub const fn num_nodes(&self) -> usize {
+        self.configurations.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        geo::{Neighborhood, RadiusNeighborhood},
+        metric::{Euclidean, SquaredEuclidean},
+        nn::KdTreeMap,
+        sample::{InformedSet, Rectangle},
+        space::Vector,
+        time::LimitNodes,
+        valid::AlwaysValid,
+    };
+    use rand::{distributions::Bernoulli, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    #[test]
+    fn converges() {
+        let mut rrt = RrtStar::new(
+            Vector::new([0.0, 0.0]),
+            KdTreeMap::new(SquaredEuclidean),
+            &AlwaysValid,
+            Euclidean,
+        );
+        let space = Rectangle {
+            min: Vector::new([0.0; 2]),
+            max: Vector::new([1.0; 2]),
+        };
+        let goal = Vector::new([1.0, 1.0]);
+        let nbhd = Neighborhood::ShrinkingRadius {
+            gamma: 1.0,
+            dimension: 1.0,
+        };
+        let radius = 0.05;
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let bias = Bernoulli::new(0.05).unwrap();
+
+        let first = rrt
+            .grow_toward(
+                &space,
+                &goal,
+                radius,
+                &nbhd,
+                &mut LimitNodes::new(500),
+                &bias,
+                &mut rng,
+            )
+            .unwrap();
+        let first_cost = *rrt.best_cost().unwrap();
+        assert_eq!(first.first(), Some(&Vector::new([0.0, 0.0])));
+        assert_eq!(first.last(), Some(&goal));
+
+        let second = rrt
+            .grow_toward(
+                &space,
+                &goal,
+                radius,
+                &nbhd,
+                &mut LimitNodes::new(2_000),
+                &bias,
+                &mut rng,
+            )
+            .unwrap();
+        let second_cost = *rrt.best_cost().unwrap();
+
+        // no path is shorter than the straight line to the goal
+        assert!(second_cost >= core::f64::consts::SQRT_2 - 1e-9);
+        assert!(second_cost <= first_cost);
+        assert!(second_cost <= 1.05 * core::f64::consts::SQRT_2);
+        let path_cost: f64 = second
+            .windows(2)
+            .map(|w| Euclidean.distance(&w[0], &w[1]))
+            .sum();
+        assert!((path_cost - second_cost).abs() <= 1e-9);
+    }
+
+    #[test]
+    fn informed() {
+        let space = Rectangle {
+            min: Vector::new([0.0; 2]),
+            max: Vector::new([1.0; 2]),
+        };
+        let start = Vector::new([0.0, 0.0]);
+        let goal = Vector::new([1.0, 1.0]);
+        let path = informed_rrt_star(
+            start,
+            KdTreeMap::new(SquaredEuclidean),
+            &AlwaysValid,
+            Euclidean,
+            &mut InformedSet::new(space, start, goal),
+            &goal,
+            0.05,
+            &RadiusNeighborhood::ShrinkingRadius {
+                gamma: 1.0,
+                dimension: 1.0,
+            },
+            &mut LimitNodes::new(2_000),
+            &Bernoulli::new(0.05).unwrap(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        )
+        .unwrap();
+
+        let path_cost: f64 = path
+            .windows(2)
+            .map(|w| Euclidean.distance(&w[0], &w[1]))
+            .sum();
+
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path_cost >= core::f64::consts::SQRT_2 - 1e-9);
+        assert!(path_cost <= 1.02 * core::f64::consts::SQRT_2);
+    }
+
+    #[test]
+    fn root_at_goal() {
+        let goal = Vector::new([1.0, 1.0]);
+        let mut rrt = RrtStar::new(
+            goal,
+            KdTreeMap::new(SquaredEuclidean),
+            &AlwaysValid,
+            Euclidean,
+        );
+        let path = rrt.grow_toward(
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &goal,
+            0.05,
+            &RadiusNeighborhood::Radius(0.1),
+            &mut LimitNodes::new(100),
+            &Bernoulli::new(0.05).unwrap(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        );
+        assert_eq!(path, Some(vec![goal]));
+        assert_eq!(rrt.best_cost(), Some(&0.0));
+    }
+}
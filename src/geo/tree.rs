@@ -0,0 +1,173 @@
+//! Read-only views of search trees.
+
+use alloc::vec::Vec;
+use num_traits::Zero;
+
+use crate::metric::Metric;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A read-only view of a search tree, such as that of an [`Rrt`](super::Rrt) or either half of
+/// an [`RrtConnect`](super::RrtConnect).
+///
+/// Each node of the tree is identified by its index, in the order the nodes were added.
+/// A tree may have multiple roots, each of which has no parent.
+/// Every parent is added before its children, so a parent's ID is always less than its child's.
+pub struct Tree<'t, C> {
+    configurations: &'t [C],
+    /// The parent of each node; the parent of each root is `usize::MAX`.
+    parents: &'t [usize],
+}
+
+impl<'t, C> Tree<'t, C> {
+    /// Construct a view of the tree whose nodes are `configurations`, where the parent of node `i`
+    /// is `parents[i]`, or `usize::MAX` if node `i` is a root.
+    pub(super) fn new(configurations: &'t [C], parents: &'t [usize]) -> Self {
+        debug_assert_eq!(
+            configurations.len(),
+            parents.len(),
+            "number of configurations and parents must be equal"
+        );
+        Self {
+            configurations,
+            parents,
+        }
+    }
+
+    #[must_use]
+    /// Get the number of nodes in this tree.
+    pub const fn num_nodes(&self) -> usize {
+        self.configurations.len()
+    }
+
+    #[must_use]
+    /// Get the configuration of every node in this tree, indexed by node ID.
+    pub const fn configurations(&self) -> &'t [C] {
+        self.configurations
+    }
+
+    #[must_use]
+    /// Get the configuration of the node with ID `id`, or `None` if no such node exists.
+    pub fn configuration(&self, id: usize) -> Option<&'t C> {
+        self.configurations.get(id)
+    }
+
+    #[must_use]
+    /// Get the ID of the parent of the node with ID `id`.
+    /// Returns `None` if the node is a root or does not exist.
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.parents.get(id).copied().filter(|&p| p != usize::MAX)
+    }
+
+    /// Iterate over the IDs of the roots of this tree.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + 't {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|&(_, &p)| p == usize::MAX)
+            .map(|(i, _)| i)
+    }
+
+    /// Iterate over the edges of this tree as pairs of node IDs `(parent, child)`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + 't {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(|&(_, &p)| p != usize::MAX)
+            .map(|(i, &p)| (p, i))
+    }
+
+    #[must_use]
+    /// Get the number of edges between the node with ID `id` and its root.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with ID `id` exists.
+    pub fn depth(&self, mut id: usize) -> usize {
+        let mut depth = 0;
+        while self.parents[id] != usize::MAX {
+            id = self.parents[id];
+            depth += 1;
+        }
+        depth
+    }
+
+    #[must_use]
+    /// Get the total length, as measured by `metric`, of the path from the root of the node with
+    /// ID `id` to that node.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if no node with ID `id` exists.
+    pub fn cost<M: Metric<C>>(&self, mut id: usize, metric: &M) -> M::Distance {
+        let mut cost = M::Distance::zero();
+        while self.parents[id] != usize::MAX {
+            let parent = self.parents[id];
+            cost = cost + metric.distance(&self.configurations[parent], &self.configurations[id]);
+            id = parent;
+        }
+        cost
+    }
+
+    #[must_use]
+    /// Get the depth of every node in this tree, indexed by node ID.
+    ///
+    /// This is equivalent to calling [`Tree::depth`] on every node, but takes linear time.
+    pub fn depths(&self) -> Vec<usize> {
+        let mut depths: Vec<usize> = Vec::with_capacity(self.num_nodes());
+        for &p in self.parents {
+            let depth = if p == usize::MAX { 0 } else { depths[p] + 1 };
+            depths.push(depth);
+        }
+        depths
+    }
+
+    #[must_use]
+    /// Get the cost of every node in this tree as measured by `metric`, indexed by node ID.
+    ///
+    /// This is equivalent to calling [`Tree::cost`] on every node, but takes linear time.
+    pub fn costs<M>(&self, metric: &M) -> Vec<M::Distance>
+    where
+        M: Metric<C>,
+        M::Distance: Clone,
+    {
+        let mut costs: Vec<M::Distance> = Vec::with_capacity(self.num_nodes());
+        for (i, &p) in self.parents.iter().enumerate() {
+            let cost = if p == usize::MAX {
+                M::Distance::zero()
+            } else {
+                costs[p].clone() + metric.distance(&self.configurations[p], &self.configurations[i])
+            };
+            costs.push(cost);
+        }
+        costs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metric::SquaredEuclidean, space::Vector};
+
+    #[test]
+    fn two_roots() {
+        let configurations = [
+            Vector::new([0.0f64]),
+            Vector::new([1.0]),
+            Vector::new([5.0]),
+            Vector::new([3.0]),
+        ];
+        let parents = [usize::MAX, 0, usize::MAX, 1];
+        let tree = Tree::new(&configurations, &parents);
+
+        assert_eq!(tree.roots().collect::<Vec<_>>(), [0, 2]);
+        assert_eq!(tree.edges().collect::<Vec<_>>(), [(0, 1), (1, 3)]);
+        assert_eq!(tree.parent(3), Some(1));
+        assert_eq!(tree.parent(2), None);
+        assert_eq!(tree.parent(4), None);
+        assert_eq!(tree.depth(3), 2);
+        assert_eq!(tree.depths(), [0, 1, 0, 2]);
+        assert!((tree.cost(3, &SquaredEuclidean) - 5.0).abs() < 1e-12);
+        let costs = tree.costs(&SquaredEuclidean);
+        assert!((0..4).all(|i| (costs[i] - tree.cost(i, &SquaredEuclidean)).abs() < 1e-12));
+    }
+}
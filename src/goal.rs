@@ -0,0 +1,492 @@
+//! Goal specifications for motion planning problems.
+
+use core::ops::Sub;
+
+use num_traits::{float::FloatCore, Float, FloatConst, Zero};
+use rand::{distributions::uniform::SampleUniform, Rng};
+
+use crate::{
+    metric::{Euclidean, Metric},
+    sample::{Rectangle, Sample},
+    space::{Angle, Orient, Pose2d, Pose3d, Vector},
+};
+
+/// A goal for a motion planning problem: a set of configurations, any of which solves the problem.
+///
+/// Planners terminate once they reach any configuration which satisfies the goal.
+///
+/// A single configuration (a [`Vector`], [`Angle`], [`Orient`], [`Pose2d`], or [`Pose3d`]) is a
+/// goal satisfied only by itself.
+/// A tuple of goals is a goal satisfied by each tuple of configurations whose elements satisfy
+/// the goals at the same index.
+/// A [`Rectangle`] of vectors is a goal satisfied by any vector within it, or by any pose whose
+/// position is within it, regardless of orientation.
+/// Any closure `Fn(&C) -> bool` is a goal satisfied by the configurations for which it returns
+/// `true`.
+pub trait Goal<C> {
+    /// The distance between a configuration and the goal.
+    type Distance: PartialOrd + Zero;
+
+    /// Return `true` if `c` satisfies this goal.
+    fn is_satisfied(&self, c: &C) -> bool;
+
+    /// Compute the distance from `c` to the nearest configuration satisfying this goal.
+    /// The distance must be zero if `c` satisfies this goal.
+    fn distance_to_goal(&self, c: &C) -> Self::Distance;
+
+    /// Sample a configuration which satisfies this goal, using `rng` as a source of randomness.
+    ///
+    /// Returns `None` if this goal cannot be sampled, in which case planners can only solve a
+    /// problem by reaching the goal while exploring.
+    fn sample_goal<RNG: Rng>(&self, _rng: &mut RNG) -> Option<C> {
+        None
+    }
+
+    /// Return `true` if this goal is satisfied by exactly one configuration, which
+    /// [`Goal::sample_goal`] always returns.
+    ///
+    /// Planners report such a goal as invalid if its configuration is invalid, since no other
+    /// configuration can satisfy it.
+    fn is_single_state(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A goal which is satisfied by all configurations within `tolerance` of `center`, as measured by
+/// `metric`.
+pub struct Tolerance<C, M, D> {
+    /// The center of the goal region.
+    pub center: C,
+    /// The metric for distances to the center.
+    pub metric: M,
+    /// The maximum distance from `center` of a configuration satisfying the goal.
+    pub tolerance: D,
+}
+
+impl<C, M, D> Goal<C> for Tolerance<C, M, D>
+where
+    C: Clone,
+    M: Metric<C, Distance = D>,
+    D: Clone + PartialOrd + Zero + Sub<Output = D>,
+{
+    type Distance = D;
+
+    fn is_satisfied(&self, c: &C) -> bool {
+        self.metric.distance(c, &self.center) <= self.tolerance
+    }
+
+    fn distance_to_goal(&self, c: &C) -> Self::Distance {
+        let dist = self.metric.distance(c, &self.center);
+        if dist <= self.tolerance {
+            D::zero()
+        } else {
+            dist - self.tolerance.clone()
+        }
+    }
+
+    /// Always returns the center of the goal region.
+    fn sample_goal<RNG: Rng>(&self, _: &mut RNG) -> Option<C> {
+        Some(self.center.clone())
+    }
+
+    /// A goal with no tolerance is satisfied only by its center.
+    fn is_single_state(&self) -> bool {
+        self.tolerance <= D::zero()
+    }
+}
+
+impl<F, C> Goal<C> for F
+where
+    F: Fn(&C) -> bool,
+{
+    /// Since the distance to an arbitrary predicate is unknown, the distance is 0 if a
+    /// configuration satisfies the goal and 1 otherwise.
+    type Distance = u8;
+
+    fn is_satisfied(&self, c: &C) -> bool {
+        self(c)
+    }
+
+    fn distance_to_goal(&self, c: &C) -> Self::Distance {
+        u8::from(!self(c))
+    }
+}
+
+impl<const N: usize, T> Goal<Self> for Vector<N, T>
+where
+    T: Float,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Self) -> bool {
+        self == c
+    }
+
+    fn distance_to_goal(&self, c: &Self) -> Self::Distance {
+        Euclidean.distance(self, c)
+    }
+
+    fn sample_goal<RNG: Rng>(&self, _: &mut RNG) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn is_single_state(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Goal<Self> for Angle<T>
+where
+    T: FloatCore + FloatConst,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Self) -> bool {
+        self == c
+    }
+
+    fn distance_to_goal(&self, c: &Self) -> Self::Distance {
+        Euclidean.distance(self, c)
+    }
+
+    fn sample_goal<RNG: Rng>(&self, _: &mut RNG) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn is_single_state(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Goal<Self> for Orient<T>
+where
+    T: Float,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Self) -> bool {
+        self == c
+    }
+
+    fn distance_to_goal(&self, c: &Self) -> Self::Distance {
+        Euclidean.distance(self, c)
+    }
+
+    fn sample_goal<RNG: Rng>(&self, _: &mut RNG) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn is_single_state(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Goal<Self> for Pose2d<T>
+where
+    T: Float + FloatConst + FloatCore,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Self) -> bool {
+        self == c
+    }
+
+    /// The distance is the sum of the Euclidean distance between positions and the angle between
+    /// orientations.
+    fn distance_to_goal(&self, c: &Self) -> Self::Distance {
+        Euclidean.distance(&self.position, &c.position) + Euclidean.distance(&self.angle, &c.angle)
+    }
+
+    fn sample_goal<RNG: Rng>(&self, _: &mut RNG) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn is_single_state(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Goal<Self> for Pose3d<T>
+where
+    T: Float,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Self) -> bool {
+        self == c
+    }
+
+    /// The distance is the sum of the Euclidean distance between positions and the angle between
+    /// orientations.
+    fn distance_to_goal(&self, c: &Self) -> Self::Distance {
+        Euclidean.distance(&self.position, &c.position)
+            + Euclidean.distance(&self.orient, &c.orient)
+    }
+
+    fn sample_goal<RNG: Rng>(&self, _: &mut RNG) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn is_single_state(&self) -> bool {
+        true
+    }
+}
+
+/// Implement `Goal` for a tuple of goals, each constraining the element of a tuple of
+/// configurations at the same index.
+/// The distance to such a goal is the sum of the distances to each of its elements.
+macro_rules! goal_tuple {
+    ($($c:ident $g:ident $i:tt),*) => {
+        impl<T, $($c, $g: Goal<$c, Distance = T>),*> Goal<($($c,)*)> for ($($g,)*)
+        where
+            T: PartialOrd + Zero,
+        {
+            type Distance = T;
+
+            fn is_satisfied(&self, c: &($($c,)*)) -> bool {
+                true $(&& self.$i.is_satisfied(&c.$i))*
+            }
+
+            fn distance_to_goal(&self, c: &($($c,)*)) -> Self::Distance {
+                T::zero() $(+ self.$i.distance_to_goal(&c.$i))*
+            }
+
+            fn sample_goal<RNG: Rng>(&self, rng: &mut RNG) -> Option<($($c,)*)> {
+                Some(($(self.$i.sample_goal(rng)?,)*))
+            }
+
+            fn is_single_state(&self) -> bool {
+                true $(&& self.$i.is_single_state())*
+            }
+        }
+    };
+}
+
+goal_tuple!(A GA 0);
+goal_tuple!(A GA 0, B GB 1);
+goal_tuple!(A GA 0, B GB 1, C GC 2);
+goal_tuple!(A GA 0, B GB 1, C GC 2, D GD 3);
+goal_tuple!(A GA 0, B GB 1, C GC 2, D GD 3, E GE 4);
+goal_tuple!(A GA 0, B GB 1, C GC 2, D GD 3, E GE 4, F GF 5);
+goal_tuple!(A GA 0, B GB 1, C GC 2, D GD 3, E GE 4, F GF 5, G GG 6);
+goal_tuple!(A GA 0, B GB 1, C GC 2, D GD 3, E GE 4, F GF 5, G GG 6, H GH 7);
+
+impl<const N: usize, T> Rectangle<Vector<N, T>>
+where
+    T: Float,
+{
+    /// Compute the Euclidean distance from `v` to the nearest point in this rectangle.
+    fn distance_to(&self, v: &Vector<N, T>) -> T {
+        let nearest = Vector(core::array::from_fn(|i| {
+            v[i].max(self.min[i]).min(self.max[i])
+        }));
+        Euclidean.distance(v, &nearest)
+    }
+
+    /// Determine whether `v` is within this rectangle.
+    fn contains(&self, v: &Vector<N, T>) -> bool {
+        (0..N).all(|i| self.min[i] <= v[i] && v[i] <= self.max[i])
+    }
+}
+
+impl<const N: usize, T> Goal<Vector<N, T>> for Rectangle<Vector<N, T>>
+where
+    T: Float + FloatCore + SampleUniform,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Vector<N, T>) -> bool {
+        self.contains(c)
+    }
+
+    fn distance_to_goal(&self, c: &Vector<N, T>) -> Self::Distance {
+        self.distance_to(c)
+    }
+
+    fn sample_goal<RNG: Rng>(&self, rng: &mut RNG) -> Option<Vector<N, T>> {
+        Some(self.sample(rng))
+    }
+}
+
+impl<T> Goal<Pose2d<T>> for Rectangle<Vector<2, T>>
+where
+    T: Float + FloatConst + FloatCore + SampleUniform,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Pose2d<T>) -> bool {
+        self.contains(&c.position)
+    }
+
+    fn distance_to_goal(&self, c: &Pose2d<T>) -> Self::Distance {
+        self.distance_to(&c.position)
+    }
+
+    /// Samples a position uniformly within the rectangle and an angle uniformly.
+    fn sample_goal<RNG: Rng>(&self, rng: &mut RNG) -> Option<Pose2d<T>> {
+        Some(self.sample(rng))
+    }
+}
+
+impl<T> Goal<Pose3d<T>> for Rectangle<Vector<3, T>>
+where
+    T: Float + FloatConst + FloatCore + SampleUniform,
+{
+    type Distance = T;
+
+    fn is_satisfied(&self, c: &Pose3d<T>) -> bool {
+        self.contains(&c.position)
+    }
+
+    fn distance_to_goal(&self, c: &Pose3d<T>) -> Self::Distance {
+        self.distance_to(&c.position)
+    }
+
+    /// Samples a position uniformly within the rectangle and an orientation uniformly.
+    fn sample_goal<RNG: Rng>(&self, rng: &mut RNG) -> Option<Pose3d<T>> {
+        Some(self.sample(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{
+        metric::SquaredEuclidean,
+        space::{Angle, Orient},
+    };
+
+    #[test]
+    fn tolerance() {
+        let goal = Tolerance {
+            center: Vector::new([1.0, 1.0]),
+            metric: Euclidean,
+            tolerance: 0.5,
+        };
+        assert!(goal.is_satisfied(&Vector::new([1.0, 1.4])));
+        assert!(!goal.is_satisfied(&Vector::new([1.0, 2.0])));
+        assert!(f64::abs(goal.distance_to_goal(&Vector::new([1.0, 1.4]))) < 1e-12);
+        assert!(f64::abs(goal.distance_to_goal(&Vector::new([1.0, 2.0])) - 0.5) < 1e-12);
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        assert_eq!(goal.sample_goal(&mut rng), Some(goal.center));
+    }
+
+    #[test]
+    fn single_state() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let pose = Pose2d {
+            position: Vector::new([1.0, 2.0]),
+            angle: Angle::new(0.5),
+        };
+        let other = Pose2d {
+            position: Vector::new([4.0, 6.0]),
+            angle: Angle::new(1.5),
+        };
+        assert!(pose.is_satisfied(&pose));
+        assert!(!pose.is_satisfied(&other));
+        assert!(f64::abs(pose.distance_to_goal(&other) - 6.0) < 1e-12);
+        assert_eq!(pose.sample_goal(&mut rng), Some(pose));
+        assert!(pose.is_single_state());
+
+        let orient = Orient::from_euler(0.1, 0.2, 0.3);
+        assert!(orient.is_satisfied(&orient));
+        assert!(!orient.is_satisfied(&Orient::identity()));
+        assert!(orient.is_single_state());
+
+        // a tuple of goals constrains each element separately
+        let goal = (
+            Rectangle {
+                min: Vector::new([0.0, 0.0]),
+                max: Vector::new([1.0, 1.0]),
+            },
+            Angle::new(1.0),
+        );
+        assert!(goal.is_satisfied(&(Vector::new([0.5, 0.5]), Angle::new(1.0))));
+        assert!(!goal.is_satisfied(&(Vector::new([0.5, 0.5]), Angle::new(2.0))));
+        assert!(!goal.is_satisfied(&(Vector::new([2.0, 0.5]), Angle::new(1.0))));
+        assert!(
+            f64::abs(goal.distance_to_goal(&(Vector::new([2.0, 0.5]), Angle::new(2.0))) - 2.0)
+                < 1e-12
+        );
+        let (v, angle): (Vector<2>, Angle) = goal.sample_goal(&mut rng).unwrap();
+        assert!((0.0..=1.0).contains(&v[0]) && (0.0..=1.0).contains(&v[1]));
+        assert_eq!(angle, Angle::new(1.0));
+        assert!(!Goal::<(Vector<2>, Angle)>::is_single_state(&goal));
+        assert!((Vector::new([1.0]), Angle::new(1.0)).is_single_state());
+    }
+
+    #[test]
+    fn position_box() {
+        let goal = Rectangle {
+            min: Vector::new([1.0, 1.0, 1.0]),
+            max: Vector::new([2.0, 2.0, 2.0]),
+        };
+        let inside = Pose3d {
+            position: Vector::new([1.5, 1.5, 1.5]),
+            orient: Orient::from_euler(0.1, 0.2, 0.3),
+        };
+        let outside = Pose3d {
+            position: Vector::new([0.0, 1.5, 1.5]),
+            orient: Orient::identity(),
+        };
+        assert!(goal.is_satisfied(&inside));
+        assert!(!goal.is_satisfied(&outside));
+        assert!(f64::abs(goal.distance_to_goal(&inside)) < 1e-12);
+        assert!(f64::abs(goal.distance_to_goal(&outside) - 1.0) < 1e-12);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        for _ in 0..100 {
+            let pose: Pose2d = Rectangle {
+                min: Vector::new([1.0, 1.0]),
+                max: Vector::new([2.0, 2.0]),
+            }
+            .sample_goal(&mut rng)
+            .unwrap();
+            assert!((1.0..=2.0).contains(&pose.position[0]));
+            assert!((1.0..=2.0).contains(&pose.position[1]));
+        }
+    }
+
+    #[test]
+    fn predicate() {
+        let goal =
+            |c: &(Vector<2>, Angle)| SquaredEuclidean.distance(&c.0, &Vector::new([0.0; 2])) < 1.0;
+        let near = (Vector::new([0.5, 0.0]), Angle::new(1.0));
+        let far = (Vector::new([2.0, 0.0]), Angle::new(1.0));
+        assert!(goal.is_satisfied(&near));
+        assert_eq!(goal.distance_to_goal(&far), 1);
+        assert_eq!(
+            goal.sample_goal(&mut ChaCha20Rng::seed_from_u64(2707)),
+            None
+        );
+    }
+
+    #[test]
+    fn rrt_to_region() {
+        let goal = Rectangle {
+            min: Vector::new([0.9, 0.9]),
+            max: Vector::new([1.0, 1.0]),
+        };
+        let path = crate::geo::rrt(
+            Vector::new([0.0, 0.0]),
+            crate::nn::KdTreeMap::new(SquaredEuclidean),
+            &crate::valid::AlwaysValid,
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &goal,
+            0.01,
+            &mut crate::time::Solved::new(),
+            &rand::distributions::Bernoulli::new(0.05).unwrap(),
+            &mut ChaCha20Rng::seed_from_u64(2707),
+        )
+        .expect("failed to find a path");
+        assert!(goal.is_satisfied(path.last().unwrap()));
+        assert!(path[..path.len() - 1].iter().all(|c| !goal.is_satisfied(c)));
+    }
+}
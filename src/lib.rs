@@ -13,9 +13,11 @@ extern crate alloc;
 
 pub mod env;
 pub mod geo;
+pub mod goal;
 pub mod kino;
 pub mod metric;
 pub mod nn;
+pub mod path;
 pub mod sample;
 pub mod space;
 pub mod time;
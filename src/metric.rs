@@ -1,12 +1,12 @@
 //! Distance metrics.
 
-use core::{array, iter::Sum};
+use core::{array, iter::Sum, ops::Mul};
 
 use crate::{
     nn::DistanceAabb,
-    space::{Angle, Vector},
+    space::{Angle, Orient, Vector},
 };
-use num_traits::{float::FloatCore, FloatConst, Zero};
+use num_traits::{float::FloatCore, Float, FloatConst, Zero};
 
 /// A metric between configurations.
 pub trait Metric<C> {
@@ -25,6 +25,35 @@ pub struct SquaredEuclidean;
 /// The Euclidean distance metric, i.e. the length of the line segment connecting two points.
 pub struct Euclidean;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A metric for tuples of configurations which takes a weighted sum of the distances between
+/// each of their elements.
+///
+/// `M` should be a tuple of metrics, one for each element of the configuration, and `W` an array
+/// of the weight for each element.
+///
+/// # Examples
+///
+/// ```
+/// use rumple::{
+///     metric::{Euclidean, Metric, SquaredEuclidean, WeightedSum},
+///     space::{Angle, Vector},
+/// };
+/// let metric = WeightedSum {
+///     metrics: (SquaredEuclidean, Euclidean),
+///     weights: [1.0, 0.5],
+/// };
+/// let c1 = (Vector::new([0.0, 0.0]), Angle::new(0.0));
+/// let c2 = (Vector::new([1.0, 1.0]), Angle::new(1.0));
+/// assert_eq!(metric.distance(&c1, &c2), 2.5);
+/// ```
+pub struct WeightedSum<M, W> {
+    /// The metric for each element of a configuration.
+    pub metrics: M,
+    /// The weight to apply to the distance between each element of a configuration.
+    pub weights: W,
+}
+
 impl SquaredEuclidean {
     /// Computer the distance between two vectors without requiring that the result be strictly
     /// ordered.
@@ -70,6 +99,38 @@ where
     }
 }
 
+impl<T, const N: usize> Metric<Vector<N, T>> for Euclidean
+where
+    T: Float,
+{
+    type Distance = T;
+
+    fn distance(&self, c1: &Vector<N, T>, c2: &Vector<N, T>) -> Self::Distance {
+        let mut total = T::zero();
+        for (&a, &b) in c1.iter().zip(c2.iter()) {
+            total = total + (a - b) * (a - b);
+        }
+        total.sqrt()
+    }
+}
+
+impl<T, const N: usize> DistanceAabb<Vector<N, T>> for Euclidean
+where
+    T: Float,
+{
+    fn distance_to_aabb(
+        &self,
+        c: &Vector<N, T>,
+        aabb_lo: &Vector<N, T>,
+        aabb_hi: &Vector<N, T>,
+    ) -> Self::Distance {
+        Self.distance(
+            c,
+            &Vector(array::from_fn(|i| c[i].max(aabb_lo[i]).min(aabb_hi[i]))),
+        )
+    }
+}
+
 impl<T> Metric<Angle<T>> for Euclidean
 where
     T: FloatCore + FloatConst,
@@ -125,6 +186,117 @@ where
     }
 }
 
+impl<T> Metric<Orient<T>> for Euclidean
+where
+    T: Float,
+{
+    type Distance = T;
+
+    /// Compute the geodesic distance between two orientations, i.e. the angle of the smallest
+    /// rotation between them.
+    fn distance(&self, c1: &Orient<T>, c2: &Orient<T>) -> Self::Distance {
+        c1.angle_to(*c2)
+    }
+}
+
+impl<T> Metric<Orient<T>> for SquaredEuclidean
+where
+    T: Float,
+{
+    type Distance = T;
+
+    fn distance(&self, c1: &Orient<T>, c2: &Orient<T>) -> Self::Distance {
+        let d = Euclidean.distance(c1, c2);
+        d * d
+    }
+}
+
+impl<T> DistanceAabb<Orient<T>> for Euclidean
+where
+    T: Float,
+{
+    fn distance_to_aabb(
+        &self,
+        c: &Orient<T>,
+        aabb_lo: &Orient<T>,
+        aabb_hi: &Orient<T>,
+    ) -> Self::Distance {
+        // For unit quaternions separated by an angle `phi` in R^4, the chord between them has
+        // length `2 sin(phi / 2)`, and the rotation between them has angle `2 min(phi, pi - phi)`.
+        // The chord to the nearest point in the box (from either `c` or `-c`) therefore gives a
+        // lower bound on the geodesic distance to any orientation in the box.
+        let q = c.get();
+        let (lo, hi) = (aabb_lo.get(), aabb_hi.get());
+        let (mut pos_sq, mut neg_sq) = (T::zero(), T::zero());
+        for i in 0..4 {
+            let pos = q[i] - q[i].max(lo[i]).min(hi[i]);
+            let neg = q[i] + (-q[i]).max(lo[i]).min(hi[i]);
+            pos_sq = pos_sq + pos * pos;
+            neg_sq = neg_sq + neg * neg;
+        }
+        let half_chord = pos_sq.min(neg_sq).sqrt() / (T::one() + T::one());
+        half_chord.min(T::one()).asin() * T::from(4).unwrap()
+    }
+}
+
+impl<T> DistanceAabb<Orient<T>> for SquaredEuclidean
+where
+    T: Float,
+{
+    fn distance_to_aabb(
+        &self,
+        c: &Orient<T>,
+        aabb_lo: &Orient<T>,
+        aabb_hi: &Orient<T>,
+    ) -> Self::Distance {
+        let d = Euclidean.distance_to_aabb(c, aabb_lo, aabb_hi);
+        d * d
+    }
+}
+
+/// Implement `Metric` and `DistanceAabb` for a `WeightedSum` over a tuple of configurations.
+macro_rules! weighted_sum_tuple {
+    ($n:literal; $($c:ident $m:ident $i:tt),*) => {
+        impl<T, $($c, $m: Metric<$c, Distance = T>),*> Metric<($($c,)*)>
+            for WeightedSum<($($m,)*), [T; $n]>
+        where
+            T: Copy + PartialOrd + Zero + Mul<Output = T>,
+        {
+            type Distance = T;
+
+            fn distance(&self, c1: &($($c,)*), c2: &($($c,)*)) -> Self::Distance {
+                T::zero() $(+ self.weights[$i] * self.metrics.$i.distance(&c1.$i, &c2.$i))*
+            }
+        }
+
+        impl<T, $($c, $m: DistanceAabb<$c, Distance = T>),*> DistanceAabb<($($c,)*)>
+            for WeightedSum<($($m,)*), [T; $n]>
+        where
+            T: Copy + PartialOrd + Zero + Mul<Output = T>,
+        {
+            fn distance_to_aabb(
+                &self,
+                c: &($($c,)*),
+                aabb_lo: &($($c,)*),
+                aabb_hi: &($($c,)*),
+            ) -> Self::Distance {
+                T::zero()
+                    $(+ self.weights[$i]
+                        * self.metrics.$i.distance_to_aabb(&c.$i, &aabb_lo.$i, &aabb_hi.$i))*
+            }
+        }
+    };
+}
+
+weighted_sum_tuple!(1; A MA 0);
+weighted_sum_tuple!(2; A MA 0, B MB 1);
+weighted_sum_tuple!(3; A MA 0, B MB 1, C MC 2);
+weighted_sum_tuple!(4; A MA 0, B MB 1, C MC 2, D MD 3);
+weighted_sum_tuple!(5; A MA 0, B MB 1, C MC 2, D MD 3, E ME 4);
+weighted_sum_tuple!(6; A MA 0, B MB 1, C MC 2, D MD 3, E ME 4, F MF 5);
+weighted_sum_tuple!(7; A MA 0, B MB 1, C MC 2, D MD 3, E ME 4, F MF 5, G MG 6);
+weighted_sum_tuple!(8; A MA 0, B MB 1, C MC 2, D MD 3, E ME 4, F MF 5, G MG 6, H MH 7);
+
 impl Default for SquaredEuclidean {
     fn default() -> Self {
         Self
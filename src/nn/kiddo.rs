@@ -8,9 +8,8 @@ use num_traits::float::FloatCore;
 
 use crate::{nn::NearestNeighborsMap, space::Vector};
 
-use super::RangeNearestNeighborsMap;
+use super::{DynamicNearestNeighborsMap, KNearestNeighborsMap, RangeNearestNeighborsMap};
 
-#[expect(clippy::module_name_repetitions)]
 #[derive(Clone, Debug)]
 /// A _k_-d tree map using [`kiddo::KdTree`] as its backing implementation.
 ///
@@ -42,6 +41,8 @@ impl<T, const N: usize, V> NearestNeighborsMap<Vector<N, T>, V>
 where
     T: FloatCore + Default + AddAssign + Send + Sync + Axis,
 {
+    type Distance = T;
+
     fn insert(&mut self, key: Vector<N, T>, value: V) {
         self.tree.add(&key, self.values.len());
         self.values.push(value);
@@ -56,9 +57,53 @@ where
     }
 }
 
-#[expect(clippy::module_name_repetitions)]
+impl<T, const N: usize, V> KiddoMap<T, N, V, crate::metric::SquaredEuclidean>
+where
+    T: FloatCore + Default + AddAssign + Send + Sync + Axis,
+{
+    /// Find the index of an element with key `key`.
+    fn find(&self, key: &Vector<N, T>) -> Option<usize> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let item = self.tree.nearest_one::<kiddo::SquaredEuclidean>(key).item;
+        (&self.keys[item] == key).then_some(item)
+    }
+}
+
+impl<T, const N: usize, V> DynamicNearestNeighborsMap<Vector<N, T>, V>
+    for KiddoMap<T, N, V, crate::metric::SquaredEuclidean>
+where
+    T: FloatCore + Default + AddAssign + Send + Sync + Axis,
+{
+    fn get_mut(&mut self, key: &Vector<N, T>) -> Option<&mut V> {
+        let item = self.find(key)?;
+        Some(&mut self.values[item])
+    }
+
+    fn remove(&mut self, key: &Vector<N, T>) -> Option<V> {
+        let item = self.find(key)?;
+        let last = self.values.len() - 1;
+        self.tree.remove(key, item);
+        if item != last {
+            // move the last element into the removed element's slot
+            self.tree.remove(&self.keys[last], last);
+            self.tree.add(&self.keys[last], item);
+        }
+        self.keys.swap_remove(item);
+        Some(self.values.swap_remove(item))
+    }
+
+    fn clear(&mut self) {
+        self.tree = KdTree::new();
+        self.keys.clear();
+        self.values.clear();
+    }
+}
+
 pub struct KiddoNearest<'a, T, const N: usize, V, M> {
     iter: WithinUnsortedIter<'a, T, usize>,
+    keys: &'a [Vector<N, T>],
     values: &'a [V],
     _phantom: PhantomData<M>,
 }
@@ -68,7 +113,6 @@ impl<T, const N: usize, V> RangeNearestNeighborsMap<Vector<N, T>, V>
 where
     T: FloatCore + Default + AddAssign + Send + Sync + Axis,
 {
-    type Distance = T;
     type RangeNearest<'q> = KiddoNearest<'q, T, N, V, crate::metric::SquaredEuclidean> where Self: 'q;
     fn nearest_within_r<'q>(
         &'q self,
@@ -79,16 +123,42 @@ where
             iter: self
                 .tree
                 .within_unsorted_iter::<kiddo::SquaredEuclidean>(key, r),
+            keys: &self.keys,
             values: &self.values,
             _phantom: PhantomData,
         }
     }
 }
 
+impl<T, const N: usize, V> KNearestNeighborsMap<Vector<N, T>, V>
+    for KiddoMap<T, N, V, crate::metric::SquaredEuclidean>
+where
+    T: FloatCore + Default + AddAssign + Send + Sync + Axis,
+{
+    fn k_nearest<'q>(
+        &'q self,
+        key: &Vector<N, T>,
+        k: usize,
+    ) -> Vec<(&'q Vector<N, T>, &'q V, Self::Distance)> {
+        // `nearest_n` allocates room for `k` neighbors up front
+        let k = k.min(self.values.len());
+        if k == 0 {
+            return Vec::new();
+        }
+        self.tree
+            .nearest_n::<kiddo::SquaredEuclidean>(key, k)
+            .into_iter()
+            .map(|nbr| (&self.keys[nbr.item], &self.values[nbr.item], nbr.distance))
+            .collect()
+    }
+}
+
 impl<'a, T, const N: usize, V, M> Iterator for KiddoNearest<'a, T, N, V, M> {
-    type Item = &'a V;
+    type Item = (&'a Vector<N, T>, &'a V, T);
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|nbr| &self.values[nbr.item])
+        self.iter
+            .next()
+            .map(|nbr| (&self.keys[nbr.item], &self.values[nbr.item], nbr.distance))
     }
 }
 
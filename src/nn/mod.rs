@@ -1,7 +1,7 @@
 //! Nearest-neighbor search.
 
 use alloc::{boxed::Box, vec::Vec};
-use core::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+use core::{cmp::Ordering, fmt::Debug};
 use num_traits::Zero;
 
 use crate::metric::Metric;
@@ -13,6 +13,9 @@ pub use kiddo::{KiddoMap, KiddoNearest};
 
 /// A key-value map which is capable of nearest-neighbor search.
 pub trait NearestNeighborsMap<K, V> {
+    /// The distance between keys, as measured by the map's metric.
+    type Distance: PartialOrd;
+
     /// Insert a key into the map.
     fn insert(&mut self, key: K, value: V);
     /// Get the nearest element of the space to this key.
@@ -20,19 +23,63 @@ pub trait NearestNeighborsMap<K, V> {
 }
 
 /// A key-value map which is capable of range nearest-neighbor search.
+///
+/// The distances yielded by range queries are measured by the map's own metric, which is often
+/// chosen for cheap queries (such as [`SquaredEuclidean`](crate::metric::SquaredEuclidean)) rather
+/// than to measure the cost of a path.
+/// Planners which measure path costs with a separate metric must therefore recompute the
+/// distances to each neighbor instead of reusing these.
 pub trait RangeNearestNeighborsMap<K, V>: NearestNeighborsMap<K, V> {
-    /// The radius of a ball to search.
-    type Distance;
-
-    /// An iterator over configurations within a fixed radius of a query.
-    type RangeNearest<'q>: Iterator<Item = &'q V>
+    /// An iterator over the elements within a fixed radius of a query, along with their
+    /// distances to it.
+    type RangeNearest<'q>: Iterator<Item = (&'q K, &'q V, Self::Distance)>
     where
         V: 'q,
         K: 'q,
         Self: 'q;
 
-    /// Get an iterator over all items in `self` within range `r` of
+    /// Get an iterator over all elements of `self` within range `r` of `key`, along with their
+    /// distances to `key`.
+    /// The elements are not yielded in any particular order.
     fn nearest_within_r<'q>(&'q self, key: &'q K, r: Self::Distance) -> Self::RangeNearest<'q>;
+
+    /// Get all elements of `self` within range `r` of `key`, along with their distances to `key`,
+    /// ordered from nearest to farthest.
+    fn nearest_within_r_sorted<'q>(
+        &'q self,
+        key: &'q K,
+        r: Self::Distance,
+    ) -> Vec<(&'q K, &'q V, Self::Distance)> {
+        let mut nbrs = self.nearest_within_r(key, r).collect::<Vec<_>>();
+        nbrs.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+        nbrs
+    }
+}
+
+/// A key-value map which is capable of nearest-neighbor search and whose elements can be removed
+/// or updated after insertion.
+///
+/// Elements are addressed by their keys rather than by handles returned from
+/// [`NearestNeighborsMap::insert`].
+/// A map is free to move its elements around as it is modified (for instance, when rebalancing or
+/// when filling the slot of a removed element), so a handle would have to be kept up to date by
+/// every implementation, while a key always locates its element through a nearest-neighbor query.
+pub trait DynamicNearestNeighborsMap<K, V>: NearestNeighborsMap<K, V> {
+    /// Get a mutable reference to the value of `key`, or `None` if `key` is not in the map.
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+    /// Remove `key` from the map, returning its value, or `None` if `key` is not in the map.
+    /// If `key` was inserted more than once, only one of its values is removed.
+    fn remove(&mut self, key: &K) -> Option<V>;
+    /// Remove every element from the map.
+    fn clear(&mut self);
+}
+
+/// A key-value map which is capable of _k_-nearest-neighbor search.
+pub trait KNearestNeighborsMap<K, V>: NearestNeighborsMap<K, V> {
+    /// Get the `k` elements of `self` nearest to `key`, ordered from nearest to farthest, along
+    /// with their distances to `key`.
+    /// If `self` has fewer than `k` elements, all of them are returned.
+    fn k_nearest<'q>(&'q self, key: &K, k: usize) -> Vec<(&'q K, &'q V, Self::Distance)>;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -40,9 +87,22 @@ pub trait RangeNearestNeighborsMap<K, V>: NearestNeighborsMap<K, V> {
 ///
 /// This implementation is not particularly efficient, but it has support for spaces of weird
 /// topologies (such as [`crate::space::Angle`]).
+///
+/// Removed elements are only marked as removed and left in the tree until they outnumber the
+/// remaining elements, at which point the tree is rebuilt.
+///
+/// Elements inserted one at a time are added as leaves, so a tree built from sorted or clustered
+/// keys can become very deep unless it is built in bulk with [`KdTreeMap::build`] or rebalancing
+/// is enabled with [`KdTreeMap::with_rebalancing`].
 pub struct KdTreeMap<K, V, M> {
     root: Option<Node<K, V>>,
     metric: M,
+    /// Whether to rebuild unbalanced subtrees after insertion.
+    rebalance: bool,
+    /// The number of elements in the map.
+    len: usize,
+    /// The number of removed elements which are still stored in the tree.
+    n_removed: usize,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -82,14 +142,61 @@ pub trait DistanceAabb<C>: Metric<C> {
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct Node<K, V> {
     key: K,
-    value: V,
+    /// The value of this node, or `None` if it has been removed.
+    value: Option<V>,
     children: [Option<Box<Self>>; 2],
 }
 
 impl<K, V, M> KdTreeMap<K, V, M> {
     /// Construct a new `KdTreeMap` using the provided metric.
     pub const fn new(metric: M) -> Self {
-        Self { root: None, metric }
+        Self {
+            root: None,
+            metric,
+            rebalance: false,
+            len: 0,
+            n_removed: 0,
+        }
+    }
+
+    #[must_use]
+    /// Set whether this map rebalances itself as elements are inserted.
+    ///
+    /// If enabled, whenever an insertion makes the tree deeper than twice the base-2 logarithm
+    /// of its number of nodes, the subtree of the deepest ancestor of the new node which is
+    /// similarly too tall is rebuilt, as in a scapegoat tree.
+    /// This keeps nearest-neighbor queries fast when keys are inserted in sorted or clustered
+    /// order, at the cost of occasionally slower insertions.
+    /// By default, rebalancing is disabled.
+    pub const fn with_rebalancing(mut self, rebalance: bool) -> Self {
+        self.rebalance = rebalance;
+        self
+    }
+
+    #[must_use]
+    /// Get the number of elements in this map.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    /// Return `true` if this map has no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: KdKey, V, M> KdTreeMap<K, V, M> {
+    /// Construct a balanced `KdTreeMap` containing `elements`, using the provided metric.
+    ///
+    /// Each node splits its subtree at the median of its keys, so the depth of the tree is
+    /// logarithmic in its size regardless of the order of `elements`.
+    pub fn build(metric: M, elements: impl IntoIterator<Item = (K, V)>) -> Self {
+        let elements: Vec<(K, V)> = elements.into_iter().collect();
+        let mut map = Self::new(metric);
+        map.len = elements.len();
+        map.root = Node::build(elements, 0).map(|root| *root);
+        map
     }
 }
 
@@ -98,7 +205,11 @@ where
     M: DistanceAabb<K>,
     K: KdKey,
 {
+    type Distance = <M as Metric<K>>::Distance;
+
     fn insert(&mut self, key: K, value: V) {
+        self.len += 1;
+        let value = Some(value);
         let Some(mut parent) = self.root.as_mut() else {
             self.root = Some(Node {
                 key,
@@ -108,10 +219,15 @@ where
             return;
         };
 
+        // the side taken at each node on the way to the new node, if rebalancing
+        let mut path = Vec::new();
         let mut k = 0;
         let mut side: usize;
         while {
             side = parent.key.compare(&key, k).is_le().into();
+            if self.rebalance {
+                path.push(side);
+            }
             parent.children[side].is_some()
         } {
             parent = parent.children[side]
@@ -124,36 +240,105 @@ where
             value,
             children: [None, None],
         }));
+
+        if self.rebalance && path.len() > 2 * (self.len + self.n_removed).ilog2() as usize {
+            self.rebalance_path(&path);
+        }
     }
 
     fn nearest<'q>(&'q self, key: &K) -> Option<(&'q K, &'q V)> {
-        let root = self.root.as_ref()?;
-        let mut radius = self.metric.distance(&root.key, key);
-        if radius.is_zero() {
-            return Some((&root.key, &root.value));
+        let mut best = None;
+        self.nearest_help(
+            self.root.as_ref()?,
+            key,
+            K::lower_bound(),
+            K::upper_bound(),
+            &mut best,
+            0,
+        );
+        best.map(|(_, k, v)| (k, v))
+    }
+}
+
+impl<K, V, M> DynamicNearestNeighborsMap<K, V> for KdTreeMap<K, V, M>
+where
+    M: DistanceAabb<K>,
+    K: KdKey,
+{
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find_mut(key)?.value.as_mut()
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.find_mut(key)?.value.take();
+        self.len -= 1;
+        self.n_removed += 1;
+        if self.n_removed > self.len {
+            self.rebuild();
         }
-        let best_node = self
-            .nearest_help(
-                root,
-                key,
-                K::lower_bound(),
-                K::upper_bound(),
-                &mut radius,
-                0,
-            )
-            .unwrap_or(root);
-        Some((&best_node.key, &best_node.value))
+        value
+    }
+
+    fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+        self.n_removed = 0;
     }
 }
 
-// TODO make this a resuming iterator
-/// An iterator over all points with a given radius of a query point in a [`KdTreeMap`].
-pub struct RangeNearest<'a, K, V, M>(Vec<&'a V>, PhantomData<&'a KdTreeMap<K, V, M>>);
+/// An iterator over all elements within a given radius of a query point in a [`KdTreeMap`],
+/// along with their distances to the query point.
+///
+/// The tree is searched lazily, so stopping iteration early skips the rest of the search.
+pub struct RangeNearest<'a, K, V, M: Metric<K>> {
+    metric: &'a M,
+    key: &'a K,
+    radius: M::Distance,
+    /// The nodes whose subtrees remain to be searched, along with the lower and upper corners
+    /// of the regions containing them and the axes they split along.
+    stack: Vec<(&'a Node<K, V>, K, K, usize)>,
+}
 
-impl<'a, K, V, M> Iterator for RangeNearest<'a, K, V, M> {
-    type Item = &'a V;
+impl<'a, K, V, M> Iterator for RangeNearest<'a, K, V, M>
+where
+    M: DistanceAabb<K>,
+    K: KdKey,
+{
+    type Item = (&'a K, &'a V, M::Distance);
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop()
+        while let Some((node, reg_lo, reg_hi, k)) = self.stack.pop() {
+            let is_left = self.key.compare(&node.key, k).is_lt();
+            let [near_child, far_child] = if is_left {
+                [node.children[0].as_deref(), node.children[1].as_deref()]
+            } else {
+                [node.children[1].as_deref(), node.children[0].as_deref()]
+            };
+
+            let new_k = (k + 1) % K::dimension();
+            if let Some(c) = far_child {
+                let (mut far_lo, mut far_hi) = (reg_lo.clone(), reg_hi.clone());
+                if is_left {
+                    far_lo.assign(&node.key, k);
+                } else {
+                    far_hi.assign(&node.key, k);
+                }
+                if self.metric.distance_to_aabb(self.key, &far_lo, &far_hi) <= self.radius {
+                    self.stack.push((c, far_lo, far_hi, new_k));
+                }
+            }
+            // search the near child first
+            if let Some(c) = near_child {
+                self.stack.push((c, reg_lo, reg_hi, new_k));
+            }
+
+            if let Some(value) = node.value.as_ref() {
+                let dist = self.metric.distance(self.key, &node.key);
+                if dist <= self.radius {
+                    return Some((&node.key, value, dist));
+                }
+            }
+        }
+        None
     }
 }
 
@@ -162,23 +347,41 @@ where
     M: DistanceAabb<K>,
     K: KdKey,
 {
-    type Distance = <M as Metric<K>>::Distance;
     type RangeNearest<'q> = RangeNearest<'q, K, V, M> where K: 'q, V: 'q, M: 'q;
 
     fn nearest_within_r<'q>(&'q self, key: &'q K, r: Self::Distance) -> Self::RangeNearest<'q> {
-        let mut result = Vec::new();
-        if let Some(root) = self.root.as_ref() {
-            self.nearest_r_help(
+        RangeNearest {
+            metric: &self.metric,
+            key,
+            radius: r,
+            stack: self
+                .root
+                .iter()
+                .map(|root| (root, K::lower_bound(), K::upper_bound(), 0))
+                .collect(),
+        }
+    }
+}
+
+impl<K, V, M> KNearestNeighborsMap<K, V> for KdTreeMap<K, V, M>
+where
+    M: DistanceAabb<K>,
+    K: KdKey,
+{
+    fn k_nearest<'q>(&'q self, key: &K, k: usize) -> Vec<(&'q K, &'q V, Self::Distance)> {
+        let mut best = Vec::with_capacity(k.min(self.len()));
+        if let Some(root) = self.root.as_ref().filter(|_| k > 0) {
+            self.k_nearest_help(
                 key,
-                &mut result,
-                &r,
+                k,
+                &mut best,
                 root,
                 K::lower_bound(),
                 K::upper_bound(),
                 0,
             );
         }
-        RangeNearest(result, PhantomData)
+        best.into_iter().map(|(d, k, v)| (k, v, d)).collect()
     }
 }
 
@@ -187,81 +390,153 @@ where
     M: DistanceAabb<K>,
     K: KdKey,
 {
+    /// Find the node with key `key` which has not been removed.
+    fn find_mut(&mut self, key: &K) -> Option<&mut Node<K, V>> {
+        let mut node = self.root.as_mut()?;
+        let mut k = 0;
+        loop {
+            if node.value.is_some() && (0..K::dimension()).all(|a| node.key.compare(key, a).is_eq())
+            {
+                return Some(node);
+            }
+            // keys equal to a node's key along its axis are inserted to its right
+            let side = usize::from(node.key.compare(key, k).is_le());
+            node = node.children[side].as_deref_mut()?;
+            k = (k + 1) % K::dimension();
+        }
+    }
+
+    /// Rebuild the tree, discarding all removed elements.
+    fn rebuild(&mut self) {
+        let mut elements = Vec::with_capacity(self.len);
+        if let Some(root) = self.root.take() {
+            root.drain_into(&mut elements);
+        }
+        self.n_removed = 0;
+        self.root = Node::build(elements, 0).map(|root| *root);
+    }
+
+    /// Rebalance the tree after inserting the node reached by taking the child on each side in
+    /// `path` from the root.
+    ///
+    /// The subtree of the deepest ancestor of the new node whose height is more than twice the
+    /// base-2 logarithm of its size is rebuilt.
+    fn rebalance_path(&mut self, path: &[usize]) {
+        let Some(mut node) = self.root.as_ref() else {
+            return;
+        };
+        let mut ancestors = Vec::with_capacity(path.len());
+        for &side in path {
+            ancestors.push(node);
+            node = node.children[side]
+                .as_deref()
+                .expect("path must lead to an extant node");
+        }
+
+        // the root is always too tall if this was called
+        let mut scapegoat = 0;
+        let mut size = 1;
+        for (depth, (ancestor, &side)) in ancestors.iter().zip(path).enumerate().rev() {
+            size += 1 + Node::size(ancestor.children[1 - side].as_deref());
+            if path.len() - depth > 2 * size.ilog2() as usize {
+                scapegoat = depth;
+                break;
+            }
+        }
+        if scapegoat == 0 {
+            self.rebuild();
+            return;
+        }
+
+        let mut node = self.root.as_mut().expect("root was checked to exist");
+        for &side in &path[..scapegoat - 1] {
+            node = node.children[side]
+                .as_deref_mut()
+                .expect("path must lead to an extant node");
+        }
+        let subtree = &mut node.children[path[scapegoat - 1]];
+        let mut elements = Vec::with_capacity(size);
+        self.n_removed -= subtree
+            .take()
+            .expect("path must lead to an extant node")
+            .drain_into(&mut elements);
+        *subtree = Node::build(elements, scapegoat % K::dimension());
+    }
+
+    /// Search for the nearest neighbor of `key` among `node` and its descendants, which are all
+    /// within the region from `reg_lo` to `reg_hi`.
+    /// `best` contains the nearest neighbor found so far and its distance to `key`.
     fn nearest_help<'q>(
         &self,
         node: &'q Node<K, V>,
         key: &K,
         mut reg_lo: K,
         mut reg_hi: K,
-        radius: &mut <M as Metric<K>>::Distance,
+        best: &mut Option<(M::Distance, &'q K, &'q V)>,
         k: usize,
-    ) -> Option<&'q Node<K, V>> {
-        let mut best_result = None;
-        let is_right = node.key.compare(key, k).is_le();
-        // search right side first
-        let children = if is_right { [1, 0] } else { [0, 1] }.map(|i| node.children[i].as_deref());
-
-        if let Some(child) = children[0] {
-            let cdist = self.metric.distance(&child.key, key);
-            if cdist <= *radius {
-                *radius = cdist;
-                best_result = Some(child);
-                if radius.is_zero() {
+    ) {
+        if let Some(value) = node.value.as_ref() {
+            let dist = self.metric.distance(&node.key, key);
+            if best.as_ref().is_none_or(|(d, ..)| &dist < d) {
+                let exact = dist.is_zero();
+                *best = Some((dist, &node.key, value));
+                if exact {
                     // exact match to query
-                    return best_result;
+                    return;
                 }
             }
+        }
 
-            best_result = self
-                .nearest_help(
-                    child,
-                    key,
-                    reg_lo.clone(),
-                    reg_hi.clone(),
-                    radius,
-                    (k + 1) % K::dimension(),
-                )
-                .or(best_result);
-        }
-        if let Some(child) = children[1] {
-            let cdist = self.metric.distance(&child.key, key);
-            if cdist <= *radius {
-                *radius = cdist;
-                best_result = Some(child);
-                if radius.is_zero() {
-                    // exact match to query
-                    return best_result;
-                }
+        let is_left = key.compare(&node.key, k).is_lt();
+        let [near_child, far_child] = if is_left {
+            [node.children[0].as_deref(), node.children[1].as_deref()]
+        } else {
+            [node.children[1].as_deref(), node.children[0].as_deref()]
+        };
+
+        let new_k = (k + 1) % K::dimension();
+        if let Some(c) = near_child {
+            self.nearest_help(c, key, reg_lo.clone(), reg_hi.clone(), best, new_k);
+            if best.as_ref().is_some_and(|(d, ..)| d.is_zero()) {
+                return;
             }
+        }
 
-            if is_right {
-                reg_hi.assign(&node.key, k);
-            } else {
+        if let Some(c) = far_child {
+            if is_left {
                 reg_lo.assign(&node.key, k);
+            } else {
+                reg_hi.assign(&node.key, k);
             }
-            if self.metric.distance_to_aabb(key, &reg_lo, &reg_hi) < *radius {
-                best_result = self
-                    .nearest_help(child, key, reg_lo, reg_hi, radius, (k + 1) % K::dimension())
-                    .or(best_result);
+            if best
+                .as_ref()
+                .is_none_or(|(d, ..)| &self.metric.distance_to_aabb(key, &reg_lo, &reg_hi) < d)
+            {
+                self.nearest_help(c, key, reg_lo, reg_hi, best, new_k);
             }
         }
-
-        best_result
     }
 
     #[expect(clippy::too_many_arguments)]
-    fn nearest_r_help<'q>(
+    /// Search for the `n` nearest neighbors of `point`.
+    /// `best` is kept sorted by distance, with at most `n` elements.
+    fn k_nearest_help<'q>(
         &'q self,
         point: &K,
-        buf: &mut Vec<&'q V>,
-        radius: &<M as Metric<K>>::Distance,
+        n: usize,
+        best: &mut Vec<(M::Distance, &'q K, &'q V)>,
         node: &'q Node<K, V>,
         mut reg_lo: K,
         mut reg_hi: K,
         k: usize,
     ) {
-        if &self.metric.distance(point, &node.key) <= radius {
-            buf.push(&node.value);
+        if let Some(value) = node.value.as_ref() {
+            let dist = self.metric.distance(point, &node.key);
+            if best.len() < n || best.last().is_some_and(|(d, ..)| &dist < d) {
+                let idx = best.partition_point(|(d, ..)| d <= &dist);
+                best.insert(idx, (dist, &node.key, value));
+                best.truncate(n);
+            }
         }
 
         let is_left = point.compare(&node.key, k).is_lt();
@@ -273,7 +548,7 @@ where
 
         let new_k = (k + 1) % K::dimension();
         if let Some(c) = near_child {
-            self.nearest_r_help(point, buf, radius, c, reg_lo.clone(), reg_hi.clone(), new_k);
+            self.k_nearest_help(point, n, best, c, reg_lo.clone(), reg_hi.clone(), new_k);
         }
 
         if let Some(c) = far_child {
@@ -282,10 +557,85 @@ where
             } else {
                 reg_hi.assign(&node.key, k);
             }
-            if &self.metric.distance_to_aabb(point, &reg_lo, &reg_hi) <= radius {
-                self.nearest_r_help(point, buf, radius, c, reg_lo, reg_hi, new_k);
+            if best.len() < n
+                || best.last().is_some_and(|(d, ..)| {
+                    &self.metric.distance_to_aabb(point, &reg_lo, &reg_hi) < d
+                })
+            {
+                self.k_nearest_help(point, n, best, c, reg_lo, reg_hi, new_k);
+            }
+        }
+    }
+}
+
+impl<K, V> Node<K, V> {
+    /// Get the number of nodes, including removed ones, in the subtree rooted at `node`.
+    fn size(node: Option<&Self>) -> usize {
+        node.map_or(0, |n| {
+            1 + Self::size(n.children[0].as_deref()) + Self::size(n.children[1].as_deref())
+        })
+    }
+
+    /// Move every element of the subtree rooted at `self` which has not been removed into
+    /// `elements`, returning the number of removed elements discarded.
+    fn drain_into(self, elements: &mut Vec<(K, V)>) -> usize {
+        let mut n_removed = 0;
+        let mut stack = vec![self];
+        while let Some(Self {
+            key,
+            value,
+            children: [left, right],
+        }) = stack.pop()
+        {
+            match value {
+                Some(value) => elements.push((key, value)),
+                None => n_removed += 1,
+            }
+            stack.extend(right.map(|c| *c));
+            stack.extend(left.map(|c| *c));
+        }
+        n_removed
+    }
+}
+
+impl<K: KdKey, V> Node<K, V> {
+    /// Build a balanced tree containing `elements`, whose root splits along axis `k`.
+    fn build(mut elements: Vec<(K, V)>, k: usize) -> Option<Box<Self>> {
+        if elements.is_empty() {
+            return None;
+        }
+        let mid = elements.len() / 2;
+        elements.select_nth_unstable_by(mid, |a, b| a.0.compare(&b.0, k));
+        // keys equal to a node's key along its axis must be to its right, so split at the first
+        // key equal to the median
+        let mut n_less = 0;
+        for i in 0..mid {
+            if elements[i].0.compare(&elements[mid].0, k).is_lt() {
+                elements.swap(i, n_less);
+                n_less += 1;
             }
         }
+        elements.swap(n_less, mid);
+
+        let right = elements.split_off(n_less + 1);
+        let (key, value) = elements.pop().expect("median must be in elements");
+        let new_k = (k + 1) % K::dimension();
+        Some(Box::new(Self {
+            key,
+            value: Some(value),
+            children: [Self::build(elements, new_k), Self::build(right, new_k)],
+        }))
+    }
+}
+
+impl<K, V, M> FromIterator<(K, V)> for KdTreeMap<K, V, M>
+where
+    K: KdKey,
+    M: Default,
+{
+    /// Construct a balanced `KdTreeMap` with the default metric, as with [`KdTreeMap::build`].
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::build(M::default(), iter)
     }
 }
 
@@ -300,14 +650,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use rand::SeedableRng;
+    use rand::{Rng, SeedableRng};
     use rand_chacha::ChaCha20Rng;
 
     use super::*;
     use crate::{
-        metric::SquaredEuclidean,
-        sample::{Rectangle, Sample},
-        space::{Pose2d, Vector, WeightedPoseDistance},
+        metric::{Euclidean, SquaredEuclidean, WeightedSum},
+        sample::{Everywhere, Rectangle, Sample},
+        space::{Angle, Orient, Pose2d, Pose3d, Vector, WeightedPoseDistance},
         valid::AlwaysValid,
     };
 
@@ -321,6 +671,8 @@ mod tests {
     where
         M: Metric<K>,
     {
+        type Distance = M::Distance;
+
         fn insert(&mut self, key: K, value: V) {
             self.poses.push(key);
             self.values.push(value);
@@ -341,6 +693,45 @@ mod tests {
         }
     }
 
+    impl<K, V, M> KNearestNeighborsMap<K, V> for BruteForce<K, V, M>
+    where
+        M: Metric<K>,
+    {
+        fn k_nearest<'q>(&'q self, key: &K, k: usize) -> Vec<(&'q K, &'q V, Self::Distance)> {
+            let mut nbrs = self
+                .poses
+                .iter()
+                .zip(&self.values)
+                .map(|(pose, value)| (pose, value, self.metric.distance(pose, key)))
+                .collect::<Vec<_>>();
+            nbrs.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+            nbrs.truncate(k);
+            nbrs
+        }
+    }
+
+    impl<K, V, M> DynamicNearestNeighborsMap<K, V> for BruteForce<K, V, M>
+    where
+        K: PartialEq,
+        M: Metric<K>,
+    {
+        fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+            let i = self.poses.iter().position(|k| k == key)?;
+            Some(&mut self.values[i])
+        }
+
+        fn remove(&mut self, key: &K) -> Option<V> {
+            let i = self.poses.iter().position(|k| k == key)?;
+            self.poses.remove(i);
+            Some(self.values.remove(i))
+        }
+
+        fn clear(&mut self) {
+            self.poses.clear();
+            self.values.clear();
+        }
+    }
+
     fn build_tree<const N: usize>(
         points: &[[f64; N]],
     ) -> KdTreeMap<Vector<N, f64>, (), SquaredEuclidean> {
@@ -351,6 +742,12 @@ mod tests {
         t
     }
 
+    fn depth<K, V>(node: Option<&Node<K, V>>) -> usize {
+        node.map_or(0, |n| {
+            1 + depth(n.children[0].as_deref()).max(depth(n.children[1].as_deref()))
+        })
+    }
+
     #[test]
     fn make_tree() {
         let points = [[0.0, 0.0], [0.5, 0.5]];
@@ -420,6 +817,351 @@ mod tests {
         }
     }
 
+    #[test]
+    fn k_nearest() {
+        let region = Rectangle {
+            min: Vector::new([-10.0; 3]),
+            max: Vector::new([10.0; 3]),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: SquaredEuclidean,
+        };
+        let mut kdt = KdTreeMap::new(SquaredEuclidean);
+        assert!(kdt.k_nearest(&Vector::new([0.0; 3]), 3).is_empty());
+        for i in 0..1_000 {
+            let pt: Vector<3, f64> = region.sample(&mut rng);
+            bf.insert(pt, i);
+            kdt.insert(pt, i);
+            let q = region.sample(&mut rng);
+            for k in [0, 1, 5, 20] {
+                assert_eq!(bf.k_nearest(&q, k), kdt.k_nearest(&q, k));
+            }
+        }
+    }
+
+    #[test]
+    fn k_nearest_more_than_len() {
+        let keys = [Vector::new([0.0, 0.0]), Vector::new([1.0, 0.0])];
+        let q = Vector::new([0.8, 0.0]);
+        let mut kdt = KdTreeMap::new(SquaredEuclidean);
+        assert!(kdt.k_nearest(&q, usize::MAX).is_empty());
+        for (i, key) in keys.into_iter().enumerate() {
+            kdt.insert(key, i);
+        }
+        let nbrs = kdt.k_nearest(&q, usize::MAX);
+        assert_eq!(nbrs.len(), 2);
+        assert_eq!(nbrs[0].1, &1);
+        assert_eq!(nbrs[1].1, &0);
+        assert_eq!(kdt.k_nearest(&q, 3), nbrs);
+
+        #[cfg(feature = "kiddo")]
+        {
+            let mut kiddo = KiddoMap::<f64, 2, _, SquaredEuclidean>::new();
+            assert!(kiddo.k_nearest(&q, usize::MAX).is_empty());
+            for (i, key) in keys.into_iter().enumerate() {
+                kiddo.insert(key, i);
+            }
+            let values = kiddo
+                .k_nearest(&q, usize::MAX)
+                .into_iter()
+                .map(|(_, &v, _)| v)
+                .collect::<Vec<_>>();
+            assert_eq!(values, [1, 0]);
+        }
+    }
+
+    #[test]
+    fn nearest_within_r() {
+        let region = Rectangle {
+            min: Vector::new([-10.0; 3]),
+            max: Vector::new([10.0; 3]),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: SquaredEuclidean,
+        };
+        let mut kdt = KdTreeMap::new(SquaredEuclidean);
+        for i in 0..1_000 {
+            let pt: Vector<3, f64> = region.sample(&mut rng);
+            bf.insert(pt, i);
+            kdt.insert(pt, i);
+        }
+
+        for r in [0.0, 1.0, 10.0, 1_000.0] {
+            for _ in 0..100 {
+                let q = region.sample(&mut rng);
+                let mut in_range = kdt.nearest_within_r(&q, r).collect::<Vec<_>>();
+                in_range.sort_unstable_by_key(|&(_, &v, _)| v);
+                let mut bf_in_range = bf
+                    .k_nearest(&q, usize::MAX)
+                    .into_iter()
+                    .filter(|&(_, _, d)| d <= r)
+                    .collect::<Vec<_>>();
+                bf_in_range.sort_unstable_by_key(|&(_, &v, _)| v);
+                assert_eq!(bf_in_range, in_range);
+
+                // stopping early yields the same elements as a full search
+                let first = kdt.nearest_within_r(&q, r).take(3).collect::<Vec<_>>();
+                assert_eq!(
+                    first,
+                    kdt.nearest_within_r(&q, r).collect::<Vec<_>>()[..first.len()]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn remove() {
+        let region = Rectangle {
+            min: Vector::new([-10.0; 2]),
+            max: Vector::new([10.0; 2]),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: SquaredEuclidean,
+        };
+        let mut kdt = KdTreeMap::new(SquaredEuclidean);
+        let mut keys = Vec::new();
+        for i in 0..1_000 {
+            let pt: Vector<2, f64> = region.sample(&mut rng);
+            bf.insert(pt, i);
+            kdt.insert(pt, i);
+            keys.push(pt);
+        }
+
+        assert_eq!(kdt.remove(&Vector::new([20.0, 20.0])), None);
+        while keys.len() > 10 {
+            let key = keys.swap_remove(rng.gen_range(0..keys.len()));
+            assert_eq!(kdt.remove(&key), bf.remove(&key));
+            let key = keys[rng.gen_range(0..keys.len())];
+            *kdt.get_mut(&key).unwrap() += 2_000;
+            *bf.get_mut(&key).unwrap() += 2_000;
+            assert_eq!(kdt.len(), bf.values.len());
+            assert!(kdt.n_removed <= kdt.len());
+
+            let q = region.sample(&mut rng);
+            assert_eq!(bf.nearest(&q), kdt.nearest(&q));
+            assert_eq!(bf.k_nearest(&q, 5), kdt.k_nearest(&q, 5));
+            let mut in_range = kdt
+                .nearest_within_r(&q, 4.0)
+                .map(|(_, v, _)| v)
+                .collect::<Vec<_>>();
+            in_range.sort_unstable();
+            let mut bf_in_range = bf
+                .k_nearest(&q, usize::MAX)
+                .into_iter()
+                .filter(|&(_, _, d)| d <= 4.0)
+                .map(|(_, v, _)| v)
+                .collect::<Vec<_>>();
+            bf_in_range.sort_unstable();
+            assert_eq!(bf_in_range, in_range);
+        }
+
+        // a duplicate key is removed one value at a time, in no particular order
+        bf.insert(keys[0], 1_000);
+        kdt.insert(keys[0], 1_000);
+        keys.push(keys[0]);
+        let mut removed = keys
+            .iter()
+            .map(|key| kdt.remove(key).unwrap())
+            .collect::<Vec<_>>();
+        removed.sort_unstable();
+        bf.values.sort_unstable();
+        assert_eq!(removed, bf.values);
+        assert_eq!(kdt.remove(&keys[0]), None);
+        assert!(kdt.is_empty());
+        assert_eq!(kdt.nearest(&Vector::new([0.0; 2])), None);
+    }
+
+    #[cfg(feature = "kiddo")]
+    #[test]
+    fn kiddo_remove() {
+        fn without_distances<K, V>(nbrs: Vec<(K, V, f64)>) -> Vec<(K, V)> {
+            nbrs.into_iter()
+                .map(|(key, value, _)| (key, value))
+                .collect()
+        }
+
+        let region = Rectangle {
+            min: Vector::new([-10.0; 2]),
+            max: Vector::new([10.0; 2]),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: SquaredEuclidean,
+        };
+        let mut kdt = KiddoMap::<f64, 2, _, SquaredEuclidean>::new();
+        let mut keys = Vec::new();
+        for i in 0..1_000 {
+            let pt: Vector<2, f64> = region.sample(&mut rng);
+            bf.insert(pt, i);
+            kdt.insert(pt, i);
+            keys.push(pt);
+        }
+
+        assert_eq!(kdt.remove(&Vector::new([20.0, 20.0])), None);
+        while keys.len() > 10 {
+            let key = keys.swap_remove(rng.gen_range(0..keys.len()));
+            assert_eq!(kdt.remove(&key), bf.remove(&key));
+            assert_eq!(kdt.get_mut(&key), None);
+            // every remaining key, including the one moved into the removed slot, still finds
+            // its own value
+            for key in &keys {
+                assert_eq!(kdt.get_mut(key).copied(), bf.get_mut(key).copied());
+            }
+            let key = keys[rng.gen_range(0..keys.len())];
+            *kdt.get_mut(&key).unwrap() += 2_000;
+            *bf.get_mut(&key).unwrap() += 2_000;
+
+            let q = region.sample(&mut rng);
+            assert_eq!(bf.nearest(&q), kdt.nearest(&q));
+            assert_eq!(
+                without_distances(bf.k_nearest(&q, 5)),
+                without_distances(kdt.k_nearest(&q, 5))
+            );
+            let mut in_range = kdt
+                .nearest_within_r(&q, 4.0)
+                .map(|(_, v, _)| v)
+                .collect::<Vec<_>>();
+            in_range.sort_unstable();
+            let mut bf_in_range = bf
+                .k_nearest(&q, usize::MAX)
+                .into_iter()
+                .filter(|&(_, _, d)| d <= 4.0)
+                .map(|(_, v, _)| v)
+                .collect::<Vec<_>>();
+            bf_in_range.sort_unstable();
+            assert_eq!(bf_in_range, in_range);
+        }
+
+        for key in keys {
+            assert_eq!(kdt.remove(&key), bf.remove(&key));
+        }
+        assert_eq!(kdt.nearest(&Vector::new([0.0; 2])), None);
+
+        kdt.insert(Vector::new([1.0, 1.0]), 0);
+        kdt.clear();
+        assert_eq!(kdt.nearest(&Vector::new([0.0; 2])), None);
+        kdt.insert(Vector::new([1.0, 1.0]), 1);
+        assert_eq!(
+            kdt.nearest(&Vector::new([0.0; 2])),
+            Some((&Vector::new([1.0, 1.0]), &1))
+        );
+    }
+
+    #[test]
+    fn build() {
+        let region = Rectangle {
+            min: Vector::new([-10.0; 3]),
+            max: Vector::new([10.0; 3]),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: SquaredEuclidean,
+        };
+        for i in 0..1_000 {
+            let mut pt: Vector<3, f64> = region.sample(&mut rng);
+            if i % 10 == 0 {
+                // share some coordinates and keys between elements
+                pt[0] = 0.0;
+            }
+            if i % 100 == 0 {
+                pt = Vector::new([1.0; 3]);
+            }
+            bf.insert(pt, i);
+        }
+
+        let kdt: KdTreeMap<_, _, SquaredEuclidean> = bf
+            .poses
+            .iter()
+            .copied()
+            .zip(bf.values.iter().copied())
+            .collect();
+        assert_eq!(kdt.len(), 1_000);
+        // duplicate keys are chained together
+        assert!(depth(kdt.root.as_ref()) <= 20);
+        let without_values = |nbrs: Vec<(_, _, f64)>| {
+            nbrs.into_iter()
+                .map(|(key, _, dist)| (key, dist))
+                .collect::<Vec<_>>()
+        };
+        for _ in 0..1_000 {
+            let q = region.sample(&mut rng);
+            assert_eq!(bf.nearest(&q).unwrap().0, kdt.nearest(&q).unwrap().0);
+            assert_eq!(
+                without_values(bf.k_nearest(&q, 5)),
+                without_values(kdt.k_nearest(&q, 5))
+            );
+        }
+
+        let mut kdt = kdt;
+        for _ in 0..10 {
+            assert!(kdt.remove(&Vector::new([1.0; 3])).is_some());
+        }
+        assert_eq!(kdt.remove(&Vector::new([1.0; 3])), None);
+        for (pose, value) in bf.poses.iter().zip(&bf.values) {
+            if pose != &Vector::new([1.0; 3]) {
+                assert_eq!(kdt.remove(pose), Some(*value));
+            }
+        }
+        assert!(kdt.is_empty());
+    }
+
+    #[test]
+    fn rebalance() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: SquaredEuclidean,
+        };
+        let mut kdt = KdTreeMap::new(SquaredEuclidean).with_rebalancing(true);
+        // a corridor, grown along the x-axis
+        for i in 0..2_000u16 {
+            let pt = Vector::new([f64::from(i) * 0.01, rng.gen_range(0.0..0.1)]);
+            bf.insert(pt, i);
+            kdt.insert(pt, i);
+            let n = kdt.len() + kdt.n_removed;
+            assert!(depth(kdt.root.as_ref()) <= 2 * n.ilog2() as usize + 1);
+            if i % 3 == 0 {
+                let key = bf.poses[rng.gen_range(0..bf.poses.len())];
+                assert_eq!(kdt.remove(&key), bf.remove(&key));
+            }
+
+            let q = Vector::new([rng.gen_range(0.0..20.0), rng.gen_range(0.0..0.1)]);
+            assert_eq!(bf.nearest(&q), kdt.nearest(&q));
+            assert_eq!(bf.k_nearest(&q, 3), kdt.k_nearest(&q, 3));
+        }
+
+        kdt.clear();
+        assert!(kdt.is_empty());
+        assert_eq!(kdt.nearest(&Vector::new([0.0, 0.0])), None);
+        kdt.insert(Vector::new([1.0, 0.0]), 0);
+        assert_eq!(kdt.k_nearest(&Vector::new([0.0, 0.0]), 3).len(), 1);
+    }
+
     #[test]
     fn pose2d() {
         let region = Rectangle {
@@ -452,4 +1194,102 @@ mod tests {
             assert_eq!(bf_nearest, kdt_nearest);
         }
     }
+
+    #[test]
+    fn orient() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let mut random_orient = || {
+            Orient::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            )
+        };
+
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: Euclidean,
+        };
+        let mut kdt = KdTreeMap::new(Euclidean);
+        for _ in 0..2_000 {
+            let pt: Orient<f64> = random_orient();
+            bf.insert(pt, ());
+            kdt.insert(pt, ());
+            let q = random_orient();
+            let bf_nearest = bf.nearest(&q).unwrap().0;
+            let kdt_nearest = kdt.nearest(&q).unwrap().0;
+            assert_eq!(bf_nearest, kdt_nearest);
+        }
+    }
+
+    #[test]
+    fn pose3d() {
+        let region = Rectangle {
+            min: Vector::new([-10.0; 3]),
+            max: Vector::new([10.0; 3]),
+        };
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let m = WeightedPoseDistance {
+            position_metric: SquaredEuclidean,
+            position_weight: 1.0,
+            angle_metric: Euclidean,
+            angle_weight: 1.0,
+        };
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: m,
+        };
+        let mut kdt = KdTreeMap::new(m);
+        for _ in 0..2_000 {
+            let pt: Pose3d<f64> = region.sample(&mut rng);
+            bf.insert(pt, ());
+            kdt.insert(pt, ());
+            let q = region.sample(&mut rng);
+            let bf_nearest = bf.nearest(&q).unwrap().0;
+            let kdt_nearest = kdt.nearest(&q).unwrap().0;
+            assert_eq!(bf_nearest, kdt_nearest);
+        }
+    }
+
+    #[test]
+    fn product() {
+        let sampler = (
+            Rectangle {
+                min: Vector::new([-10.0; 2]),
+                max: Vector::new([10.0; 2]),
+            },
+            Everywhere,
+            Rectangle {
+                min: Vector::new([-1.0; 3]),
+                max: Vector::new([1.0; 3]),
+            },
+        );
+
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        let m = WeightedSum {
+            metrics: (SquaredEuclidean, Euclidean, SquaredEuclidean),
+            weights: [1.0, 2.0, 0.5],
+        };
+        let mut bf = BruteForce {
+            poses: Vec::new(),
+            values: Vec::new(),
+            metric: m,
+        };
+        let mut kdt = KdTreeMap::new(m);
+        for _ in 0..2_000 {
+            let pt: (Vector<2, f64>, Angle<f64>, Vector<3, f64>) = sampler.sample(&mut rng);
+            bf.insert(pt, ());
+            kdt.insert(pt, ());
+            let q = sampler.sample(&mut rng);
+            let bf_nearest = bf.nearest(&q).unwrap().0;
+            let kdt_nearest = kdt.nearest(&q).unwrap().0;
+            assert_eq!(bf_nearest, kdt_nearest);
+        }
+    }
 }
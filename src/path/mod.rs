@@ -0,0 +1,13 @@
+//! Post-processing of planned paths.
+//!
+//! Paths returned by sampling-based planners are typically jagged and full of unnecessary
+//! waypoints. This module contains tools for simplifying paths into shorter ones, for smoothing
+//! them into continuous curves, and for timing them into trajectories which can be executed.
+
+mod simplify;
+mod spline;
+mod timing;
+
+pub use simplify::{collapse_collinear, densify, reduce_vertices, shortcut, simplify};
+pub use spline::{smooth, BSpline};
+pub use timing::{Limits, Trajectory};
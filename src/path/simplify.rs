@@ -0,0 +1,256 @@
+//! Path simplification, in the spirit of OMPL's `PathSimplifier`.
+
+use alloc::vec::Vec;
+use core::ops::Add;
+
+use num_traits::Zero;
+use rand::Rng;
+
+use crate::{metric::Metric, space::Interpolate, time::Timeout, valid::GeoValidate};
+
+/// Simplify a path, returning a shorter path densified to steps of `radius`.
+///
+/// This first greedily removes unnecessary waypoints with [`reduce_vertices`], then randomly
+/// shortcuts the path with [`shortcut`] until `timeout` is over, collapses collinear waypoints
+/// with [`collapse_collinear`], and finally densifies the path with [`densify`].
+///
+/// `metric` measures the length of a path; it should obey the triangle inequality (for instance,
+/// [`crate::metric::Euclidean`] rather than [`crate::metric::SquaredEuclidean`]), or else
+/// shortcuts may not actually make paths shorter.
+pub fn simplify<C, V, M, D, R, TC, RNG>(
+    mut path: Vec<C>,
+    valid: &V,
+    metric: &M,
+    tolerance: &D,
+    radius: R,
+    timeout: &mut TC,
+    rng: &mut RNG,
+) -> Vec<C>
+where
+    C: Clone + Interpolate<Distance = R>,
+    V: GeoValidate<C>,
+    M: Metric<C, Distance = D>,
+    D: Clone + PartialOrd + Add<Output = D> + Zero,
+    R: Clone,
+    TC: Timeout,
+    RNG: Rng,
+{
+    reduce_vertices(&mut path, valid, timeout);
+    shortcut(&mut path, valid, metric, timeout, rng);
+    collapse_collinear(&mut path, valid, metric, tolerance);
+    densify(&path, radius)
+}
+
+/// Randomly shortcut a path until `timeout` is over.
+///
+/// Each attempt selects two random waypoints of the path and replaces the section between them
+/// with a direct transition if the transition is valid and shorter, as measured by `metric`.
+/// Every attempt counts as one sample against `timeout`.
+pub fn shortcut<C, V, M, D, TC, RNG>(
+    path: &mut Vec<C>,
+    valid: &V,
+    metric: &M,
+    timeout: &mut TC,
+    rng: &mut RNG,
+) where
+    V: GeoValidate<C>,
+    M: Metric<C, Distance = D>,
+    D: Clone + PartialOrd + Add<Output = D> + Zero,
+    TC: Timeout,
+    RNG: Rng,
+{
+    while path.len() > 2 && !timeout.is_over() {
+        timeout.update_sample_count(1);
+        let a = rng.gen_range(0..path.len());
+        let b = rng.gen_range(0..path.len());
+        let (i, j) = if a < b { (a, b) } else { (b, a) };
+        if j < i + 2 {
+            continue;
+        }
+
+        let section = path[i..=j]
+            .windows(2)
+            .fold(D::zero(), |len, w| len + metric.distance(&w[0], &w[1]));
+        if metric.distance(&path[i], &path[j]) < section
+            && valid.is_valid_transition(&path[i], &path[j])
+        {
+            path.drain(i + 1..j);
+        }
+    }
+}
+
+/// Greedily remove waypoints from a path.
+///
+/// Starting from the first waypoint, this connects each waypoint directly to the farthest later
+/// waypoint to which it has a valid transition, removing all waypoints in between.
+/// Every transition checked counts as one sample against `timeout`, and the reduction stops early
+/// if `timeout` is over.
+pub fn reduce_vertices<C, V, TC>(path: &mut Vec<C>, valid: &V, timeout: &mut TC)
+where
+    V: GeoValidate<C>,
+    TC: Timeout,
+{
+    let mut i = 0;
+    while i + 2 < path.len() && !timeout.is_over() {
+        if let Some(j) = (i + 2..path.len()).rev().find(|&j| {
+            timeout.update_sample_count(1);
+            valid.is_valid_transition(&path[i], &path[j])
+        }) {
+            path.drain(i + 1..j);
+        }
+        i += 1;
+    }
+}
+
+/// Remove waypoints which are collinear with their neighbors.
+///
+/// A waypoint `b` between `a` and `c` is collinear if `d(a, b) + d(b, c) <= d(a, c) + tolerance`,
+/// where `d` is `metric`.
+/// It is only removed if the transition from `a` to `c` is valid.
+pub fn collapse_collinear<C, V, M, D>(path: &mut Vec<C>, valid: &V, metric: &M, tolerance: &D)
+where
+    V: GeoValidate<C>,
+    M: Metric<C, Distance = D>,
+    D: Clone + PartialOrd + Add<Output = D>,
+{
+    if path.len() < 3 {
+        return;
+    }
+
+    let mut kept = Vec::with_capacity(path.len());
+    let mut waypoints = core::mem::take(path).into_iter();
+    let (Some(mut prev), Some(mut cur)) = (waypoints.next(), waypoints.next()) else {
+        unreachable!("path has at least 3 waypoints");
+    };
+    for next in waypoints {
+        let through = metric.distance(&prev, &cur) + metric.distance(&cur, &next);
+        let direct = metric.distance(&prev, &next) + tolerance.clone();
+        if !(through <= direct && valid.is_valid_transition(&prev, &next)) {
+            kept.push(prev);
+            prev = cur;
+        }
+        cur = next;
+    }
+    kept.push(prev);
+    kept.push(cur);
+    *path = kept;
+}
+
+/// Densify a path, inserting waypoints between each pair of consecutive waypoints by repeatedly
+/// stepping toward the next waypoint with [`Interpolate`] and `radius`, just as planners do when
+/// growing.
+pub fn densify<C, R>(path: &[C], radius: R) -> Vec<C>
+where
+    C: Clone + Interpolate<Distance = R>,
+    R: Clone,
+{
+    let Some(first) = path.first() else {
+        return Vec::new();
+    };
+    let mut dense = vec![first.clone()];
+    let mut cur = first.clone();
+    for waypoint in &path[1..] {
+        loop {
+            match cur.interpolate(waypoint, radius.clone()) {
+                Ok(next) => {
+                    dense.push(next.clone());
+                    cur = next;
+                }
+                Err(end) => {
+                    dense.push(end.clone());
+                    cur = end;
+                    break;
+                }
+            }
+        }
+    }
+    dense
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{distributions::Bernoulli, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{
+        geo::rrt,
+        metric::{Euclidean, SquaredEuclidean},
+        nn::KdTreeMap,
+        sample::Rectangle,
+        space::Vector,
+        time::{LimitSamples, Solved},
+        valid::{AlwaysValid, SampleInterpolate, Validate},
+    };
+
+    fn length(path: &[Vector<2>]) -> f64 {
+        path.windows(2)
+            .map(|w| Euclidean.distance(&w[0], &w[1]))
+            .sum()
+    }
+
+    #[test]
+    fn simplify_rrt() {
+        // a wall at x = 0.5 with a gap at the top
+        let wall = SampleInterpolate::new(
+            |c: &Vector<2>| !(0.45..0.55).contains(&c[0]) || c[1] > 0.8,
+            1e-4,
+        );
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let goal = Vector::new([0.9, 0.1]);
+        let raw = rrt(
+            Vector::new([0.1, 0.1]),
+            KdTreeMap::new(SquaredEuclidean),
+            &wall,
+            &Rectangle {
+                min: Vector::new([0.0; 2]),
+                max: Vector::new([1.0; 2]),
+            },
+            &Rectangle {
+                min: goal,
+                max: goal,
+            },
+            0.0025,
+            &mut Solved::new(),
+            &Bernoulli::new(0.05).unwrap(),
+            &mut rng,
+        )
+        .expect("unable to find path");
+
+        let mut reduced = raw.clone();
+        reduce_vertices(&mut reduced, &wall, &mut LimitSamples::new(usize::MAX));
+        assert!(reduced.len() < raw.len());
+        assert!(length(&reduced) <= length(&raw));
+
+        let simple = simplify(
+            raw.clone(),
+            &wall,
+            &Euclidean,
+            &1e-9,
+            0.0025,
+            &mut LimitSamples::new(1_000),
+            &mut rng,
+        );
+        assert_eq!(simple.first(), raw.first());
+        assert_eq!(simple.last(), raw.last());
+        assert!(length(&simple) < length(&raw));
+        assert!(simple.iter().all(|c| wall.is_valid_configuration(c)));
+    }
+
+    #[test]
+    fn collinear() {
+        let mut path = (0..=10)
+            .map(|i| Vector::new([f64::from(i), 0.0]))
+            .chain([Vector::new([10.0, 1.0])])
+            .collect::<Vec<_>>();
+        collapse_collinear(&mut path, &AlwaysValid, &Euclidean, &1e-9);
+        assert_eq!(
+            path,
+            [
+                Vector::new([0.0, 0.0]),
+                Vector::new([10.0, 0.0]),
+                Vector::new([10.0, 1.0])
+            ]
+        );
+    }
+}
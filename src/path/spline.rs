@@ -0,0 +1,312 @@
+//! Smoothing of paths with uniform cubic B-splines.
+
+use alloc::vec::Vec;
+
+use num_traits::float::FloatCore;
+
+use crate::{space::Lerp, time::Timeout, valid::GeoValidate};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// A clamped uniform cubic B-spline, which is a C2-continuous curve through configuration space.
+///
+/// The curve is controlled by a sequence of waypoints.
+/// It starts at the first waypoint and ends at the last, but in general does not pass through the
+/// waypoints in between; instead, it stays within the convex hull of every four consecutive
+/// waypoints.
+///
+/// The curve is parameterized by `u` from 0 to [`BSpline::num_segments`], with each unit interval
+/// of `u` corresponding to one polynomial segment.
+/// Curves are evaluated by repeated [`Lerp`]ing between waypoints.
+/// For configurations with angles, such as [`crate::space::Pose2d`], the curve is only smooth if
+/// every four consecutive waypoints span less than half a turn.
+pub struct BSpline<C> {
+    /// The waypoints controlling the curve.
+    waypoints: Vec<C>,
+}
+
+impl<C> BSpline<C> {
+    #[must_use]
+    /// Construct a B-spline controlled by `waypoints`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `waypoints` is empty.
+    pub fn new(waypoints: Vec<C>) -> Self {
+        assert!(
+            !waypoints.is_empty(),
+            "spline must have at least one waypoint"
+        );
+        Self { waypoints }
+    }
+
+    #[must_use]
+    /// Get the waypoints controlling this spline.
+    pub fn waypoints(&self) -> &[C] {
+        &self.waypoints
+    }
+
+    #[must_use]
+    /// Get the number of polynomial segments in this spline.
+    pub const fn num_segments(&self) -> usize {
+        self.waypoints.len() + 1
+    }
+
+    /// Get the `i`-th control point of the spline, repeating the first and last waypoints so that
+    /// the curve is clamped to them.
+    fn control(&self, i: usize) -> &C {
+        &self.waypoints[i.saturating_sub(2).min(self.waypoints.len() - 1)]
+    }
+
+    #[must_use]
+    /// Evaluate the `i`-th segment of this spline at `t`, where `t` is between 0 and 1.
+    ///
+    /// # Panics
+    ///
+    /// This function may panic if `i` is not less than [`BSpline::num_segments`].
+    pub fn evaluate_segment<T>(&self, i: usize, t: T) -> C
+    where
+        C: Lerp<Scalar = T>,
+        T: FloatCore,
+    {
+        assert!(i < self.num_segments(), "segment index out of bounds");
+        // de Boor's algorithm, specialized to uniform knots
+        let two = T::one() + T::one();
+        let three = two + T::one();
+        let q0 = self.control(i).lerp(self.control(i + 1), (t + two) / three);
+        let q1 = self
+            .control(i + 1)
+            .lerp(self.control(i + 2), (t + T::one()) / three);
+        let q2 = self.control(i + 2).lerp(self.control(i + 3), t / three);
+        let r0 = q0.lerp(&q1, (t + T::one()) / two);
+        let r1 = q1.lerp(&q2, t / two);
+        r0.lerp(&r1, t)
+    }
+
+    #[must_use]
+    /// Evaluate this spline at `u`, where `u` is between 0 and [`BSpline::num_segments`].
+    /// Values of `u` outside that range are clamped to the ends of the curve.
+    pub fn evaluate<T>(&self, u: T) -> C
+    where
+        C: Lerp<Scalar = T>,
+        T: FloatCore,
+    {
+        let i = u
+            .max(T::zero())
+            .floor()
+            .to_usize()
+            .unwrap_or(usize::MAX)
+            .min(self.num_segments() - 1);
+        let t = u - T::from(i).unwrap_or_else(T::zero);
+        self.evaluate_segment(i, t.min(T::one()))
+    }
+
+    #[must_use]
+    /// Sample this spline back into a path of configurations, taking `per_segment` evenly spaced
+    /// samples from each segment.
+    /// The returned path begins at the first waypoint and ends at the last.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `per_segment` is zero or cannot be represented as a `T`.
+    pub fn sample<T>(&self, per_segment: usize) -> Vec<C>
+    where
+        C: Lerp<Scalar = T>,
+        T: FloatCore,
+    {
+        let mut path: Vec<C> = (0..self.num_segments())
+            .flat_map(|i| self.sample_segment(i, per_segment))
+            .collect();
+        path.push(self.evaluate_segment(self.num_segments() - 1, T::one()));
+        path
+    }
+
+    /// Sample the `i`-th segment at `per_segment` evenly spaced values of `t`, excluding `t = 1`.
+    fn sample_segment<'a, T>(&'a self, i: usize, per_segment: usize) -> impl Iterator<Item = C> + 'a
+    where
+        C: Lerp<Scalar = T>,
+        T: FloatCore + 'a,
+    {
+        assert!(per_segment > 0, "must take at least one sample per segment");
+        let n = T::from(per_segment).expect("sample count must be representable");
+        (0..per_segment).map(move |k| {
+            self.evaluate_segment(
+                i,
+                T::from(k).expect("sample index must be representable") / n,
+            )
+        })
+    }
+
+    /// Determine whether the `i`-th segment is valid, checking transitions between `per_segment`
+    /// samples along it.
+    fn is_valid_segment<T, V>(&self, i: usize, valid: &V, per_segment: usize) -> bool
+    where
+        C: Lerp<Scalar = T>,
+        T: FloatCore,
+        V: GeoValidate<C>,
+    {
+        let mut prev = self.evaluate_segment(i, T::zero());
+        self.sample_segment(i, per_segment)
+            .skip(1)
+            .chain(core::iter::once(self.evaluate_segment(i, T::one())))
+            .all(|c| {
+                let ok = valid.is_valid_transition(&prev, &c);
+                prev = c;
+                ok
+            })
+    }
+}
+
+/// Fit a smooth [`BSpline`] to a path, subdividing the path until the spline is valid.
+///
+/// The path is used as the waypoints of a spline.
+/// Each segment of the spline is checked by testing the transitions between `per_segment` evenly
+/// spaced samples along it with `valid`.
+/// Then, every edge of the path near an invalid segment is split at its midpoint, which pulls the
+/// spline closer to the path, and the process repeats.
+/// Every segment checked counts as one sample against `timeout`.
+///
+/// `path` should be a valid path, such as one returned by a planner or by [`super::simplify`].
+/// As the path is subdivided, the spline converges to it, so smoothing succeeds as long as the
+/// path keeps some clearance from obstacles at its corners.
+/// Returns `None` if `path` is empty or if `timeout` is over before a valid spline is found.
+///
+/// # Panics
+///
+/// This function will panic if `per_segment` is zero or cannot be represented as a `T`.
+pub fn smooth<C, V, T, TC>(
+    path: Vec<C>,
+    valid: &V,
+    per_segment: usize,
+    timeout: &mut TC,
+) -> Option<BSpline<C>>
+where
+    C: Clone + Lerp<Scalar = T>,
+    T: FloatCore,
+    V: GeoValidate<C>,
+    TC: Timeout,
+{
+    if path.is_empty() {
+        return None;
+    }
+    let mut spline = BSpline::new(path);
+    loop {
+        // split[j] is true if the edge from waypoint j to j + 1 must be subdivided
+        let mut split = vec![false; spline.waypoints.len() - 1];
+        for i in 0..spline.num_segments() {
+            if timeout.is_over() {
+                return None;
+            }
+            timeout.update_sample_count(1);
+            if !spline.is_valid_segment(i, valid, per_segment) {
+                // segment i is controlled by waypoints i - 2 through i + 1
+                let lo = i.saturating_sub(2);
+                let hi = (i + 1).min(split.len());
+                for s in &mut split[lo.min(hi)..hi] {
+                    *s = true;
+                }
+            }
+        }
+
+        if !split.contains(&true) {
+            return Some(spline);
+        }
+        if timeout.is_over() {
+            return None;
+        }
+
+        let mut waypoints = Vec::with_capacity(2 * spline.waypoints.len());
+        for (j, c) in spline.waypoints.iter().enumerate() {
+            waypoints.push(c.clone());
+            if split.get(j).copied().unwrap_or(false) {
+                let two = T::one() + T::one();
+                waypoints.push(c.lerp(&spline.waypoints[j + 1], T::one() / two));
+            }
+        }
+        spline.waypoints = waypoints;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        space::{Angle, Pose2d, Vector},
+        time::{Forever, LimitSamples},
+        valid::SampleInterpolate,
+    };
+
+    #[test]
+    fn clamped_ends() {
+        let spline = BSpline::new(vec![
+            Vector::new([0.0, 0.0]),
+            Vector::new([1.0, 0.0]),
+            Vector::new([1.0, 1.0]),
+        ]);
+        assert_eq!(spline.num_segments(), 4);
+        assert_eq!(spline.evaluate(0.0), Vector::new([0.0, 0.0]));
+        assert_eq!(spline.evaluate(4.0), Vector::new([1.0, 1.0]));
+        let path = spline.sample(10);
+        assert_eq!(path.len(), 41);
+        assert_eq!(path.first(), Some(&Vector::new([0.0, 0.0])));
+        assert_eq!(path.last(), Some(&Vector::new([1.0, 1.0])));
+
+        // segments meet continuously
+        for i in 1..spline.num_segments() {
+            let a = spline.evaluate_segment(i - 1, 1.0);
+            let b = spline.evaluate_segment(i, 0.0);
+            assert!((a[0] - b[0]).abs() < 1e-12 && (a[1] - b[1]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn smooth_around_corner() {
+        // the path turns around the corner of a box occupying x > 0.55, y < 0.45
+        let valid = SampleInterpolate::new(|c: &Vector<2>| c[0] <= 0.55 || c[1] >= 0.45, 1e-6);
+        let path = vec![
+            Vector::new([0.0, 0.0]),
+            Vector::new([0.5, 0.0]),
+            Vector::new([0.5, 0.5]),
+            Vector::new([1.0, 0.5]),
+        ];
+        assert!(!BSpline::new(path.clone()).is_valid_segment(2, &valid, 100));
+        let spline = smooth(path, &valid, 100, &mut Forever).expect("failed to smooth path");
+        assert!(spline.waypoints().len() > 4);
+        let dense = spline.sample(100);
+        assert!(dense
+            .windows(2)
+            .all(|w| valid.is_valid_transition(&w[0], &w[1])));
+    }
+
+    #[test]
+    fn smooth_timeout() {
+        let valid = SampleInterpolate::new(|c: &Vector<2>| c[0] <= 0.55 || c[1] >= 0.45, 1e-6);
+        let path = vec![
+            Vector::new([0.0, 0.0]),
+            Vector::new([0.5, 0.0]),
+            Vector::new([0.5, 0.5]),
+            Vector::new([1.0, 0.5]),
+        ];
+        assert_eq!(smooth(path, &valid, 100, &mut LimitSamples::new(2)), None);
+    }
+
+    #[test]
+    fn pose_wraps() {
+        let spline = BSpline::new(vec![
+            Pose2d {
+                position: Vector::new([0.0, 0.0]),
+                angle: Angle::new(6.0),
+            },
+            Pose2d {
+                position: Vector::new([1.0, 0.0]),
+                angle: Angle::new(0.3),
+            },
+        ]);
+        for c in spline.sample(20) {
+            let a = c.angle.get();
+            assert!(
+                a >= 6.0 || a <= 0.3,
+                "angle {a} should take the shorter arc"
+            );
+        }
+    }
+}
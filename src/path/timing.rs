@@ -0,0 +1,532 @@
+//! Time parameterization of paths under velocity and acceleration limits.
+
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::{
+    metric::{Euclidean, Metric},
+    space::Vector,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Per-joint limits on the motion of a robot.
+pub struct Limits<const N: usize, T = f64> {
+    /// The maximum absolute velocity of each joint.
+    pub velocity: Vector<N, T>,
+    /// The maximum absolute acceleration of each joint.
+    pub acceleration: Vector<N, T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// A time-stamped trajectory following a path of straight-line segments.
+///
+/// Along each segment, the speed follows a trapezoidal profile: accelerating as hard as possible,
+/// cruising at the maximum speed, and then decelerating as hard as possible.
+/// A trajectory always starts and ends at rest.
+pub struct Trajectory<const N: usize, T = f64> {
+    /// The waypoints of the path.
+    waypoints: Vec<Vector<N, T>>,
+    /// The timing of the segment from each waypoint to the next.
+    segments: Vec<Segment<T>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The speed profile along one straight-line segment of a trajectory.
+struct Segment<T> {
+    /// The time at which the segment begins.
+    start: T,
+    /// The length of the segment.
+    length: T,
+    /// The acceleration used for speeding up and slowing down.
+    accel: T,
+    /// The speed at the start of the segment.
+    v_start: T,
+    /// The maximum speed reached along the segment.
+    v_peak: T,
+    /// The speed at the end of the segment.
+    v_end: T,
+}
+
+impl<T: Float> Segment<T> {
+    /// Construct the fastest trapezoidal profile along a segment of length `length`, with maximum
+    /// speed `v_max` and acceleration `accel`, from speed `v_start` to speed `v_end`.
+    /// The boundary speeds must be reachable from one another within the segment.
+    fn new(start: T, length: T, v_max: T, accel: T, v_start: T, v_end: T) -> Self {
+        let two = T::one() + T::one();
+        let reachable = ((two * accel * length + v_start * v_start + v_end * v_end) / two).sqrt();
+        Self {
+            start,
+            length,
+            accel,
+            v_start,
+            v_peak: v_max.min(reachable).max(v_start).max(v_end),
+            v_end,
+        }
+    }
+
+    /// Compute the time spent accelerating, cruising, and decelerating along this segment.
+    fn phases(&self) -> (T, T, T) {
+        if self.length <= T::zero() || self.v_peak <= T::zero() {
+            return (T::zero(), T::zero(), T::zero());
+        }
+        let two = T::one() + T::one();
+        let t_up = (self.v_peak - self.v_start) / self.accel;
+        let t_down = (self.v_peak - self.v_end) / self.accel;
+        let d_up = (self.v_peak + self.v_start) / two * t_up;
+        let d_down = (self.v_peak + self.v_end) / two * t_down;
+        let t_cruise = ((self.length - d_up - d_down) / self.v_peak).max(T::zero());
+        (t_up, t_cruise, t_down)
+    }
+
+    /// Compute the duration of this segment.
+    fn duration(&self) -> T {
+        let (t_up, t_cruise, t_down) = self.phases();
+        t_up + t_cruise + t_down
+    }
+
+    /// Compute the distance traveled and speed along this segment at time `t` after it begins.
+    fn state(&self, t: T) -> (T, T) {
+        let two = T::one() + T::one();
+        let (t_up, t_cruise, t_down) = self.phases();
+        let t = t.max(T::zero());
+        if t < t_up {
+            let v = self.v_start + self.accel * t;
+            ((self.v_start + v) / two * t, v)
+        } else if t < t_up + t_cruise {
+            let d_up = (self.v_start + self.v_peak) / two * t_up;
+            (d_up + self.v_peak * (t - t_up), self.v_peak)
+        } else if t < t_up + t_cruise + t_down {
+            let t_left = t_up + t_cruise + t_down - t;
+            let v = self.v_end + self.accel * t_left;
+            (self.length - (self.v_end + v) / two * t_left, v)
+        } else {
+            (self.length, self.v_end)
+        }
+    }
+}
+
+impl<const N: usize, T: Float> Trajectory<N, T> {
+    #[must_use]
+    /// Time-parameterize `path`, coming to a stop at every waypoint.
+    ///
+    /// Each segment of the path follows its own trapezoidal speed profile, starting and ending at
+    /// rest.
+    /// This is always safe to execute, since the robot never has to change direction while moving,
+    /// but is slow for paths with many waypoints.
+    pub fn trapezoidal(path: Vec<Vector<N, T>>, limits: &Limits<N, T>) -> Self {
+        let speeds = vec![T::zero(); path.len()];
+        Self::with_waypoint_speeds(path, limits, &speeds)
+    }
+
+    #[must_use]
+    /// Time-parameterize `path`, moving through waypoints as fast as the limits allow.
+    ///
+    /// This computes the maximum speed at each waypoint, then makes forward and backward passes
+    /// over the path to limit each speed to one reachable from its neighbors, in the style of
+    /// time-optimal path parameterization (TOPP).
+    ///
+    /// Since the trajectory follows straight segments, any turn taken at speed changes the
+    /// velocity instantly.
+    /// The trajectory therefore comes to a stop at every waypoint where the path turns by more
+    /// than a small tolerance (about 3 degrees).
+    /// Gentler turns are treated as a curve spread over the neighboring segments, whose
+    /// centripetal acceleration must be within the acceleration limits; the velocity of each joint
+    /// jumps by at most the speed times the turn at such a waypoint.
+    ///
+    /// This is most useful for densely-sampled smooth paths, such as those produced by
+    /// [`super::BSpline::sample`].
+    /// For jagged paths, the trajectory stops at every corner, as [`Trajectory::trapezoidal`]
+    /// does.
+    pub fn time_optimal(path: Vec<Vector<N, T>>, limits: &Limits<N, T>) -> Self {
+        let n = path.len();
+        if n < 3 {
+            return Self::trapezoidal(path, limits);
+        }
+        let two = T::one() + T::one();
+        let lengths: Vec<T> = path
+            .windows(2)
+            .map(|w| Euclidean.distance(&w[0], &w[1]))
+            .collect();
+        let dirs: Vec<Vector<N, T>> = path
+            .windows(2)
+            .zip(&lengths)
+            .map(|(w, &l)| direction(&w[0], &w[1], l))
+            .collect();
+
+        // the directions of the nearest segments of nonzero length arriving at and leaving each
+        // waypoint, so that repeated waypoints do not count as turns
+        let mut arriving = vec![None; n];
+        for k in 1..n {
+            arriving[k] = if lengths[k - 1] > T::zero() {
+                Some(dirs[k - 1])
+            } else {
+                arriving[k - 1]
+            };
+        }
+        let mut leaving = vec![None; n];
+        for k in (0..n - 1).rev() {
+            leaving[k] = if lengths[k] > T::zero() {
+                Some(dirs[k])
+            } else {
+                leaving[k + 1]
+            };
+        }
+
+        // the maximum speed at each waypoint, bounded by the speed limits of the adjacent
+        // segments and the centripetal acceleration of the turn at the waypoint
+        let max_turn = T::from(MAX_SMOOTH_TURN).unwrap_or_else(T::zero);
+        let mut speeds = vec![T::zero(); n];
+        for k in 1..n - 1 {
+            let (Some(u0), Some(u1)) = (&arriving[k], &leaving[k]) else {
+                continue;
+            };
+            if Euclidean.distance(u0, u1) > max_turn {
+                // a sharp turn: the velocity would jump, so stop at the waypoint
+                continue;
+            }
+            let ds = (lengths[k - 1] + lengths[k]) / two;
+            let mut v = speed_limit(u0, &limits.velocity).min(speed_limit(u1, &limits.velocity));
+            for j in 0..N {
+                let turn = (u1[j] - u0[j]).abs();
+                if turn > T::zero() {
+                    v = v.min((limits.acceleration[j] * ds / turn).sqrt());
+                }
+            }
+            speeds[k] = v;
+        }
+
+        // forward pass: every speed must be reachable by accelerating from the previous waypoint
+        for k in 1..n {
+            let reachable = reachable_speed(speeds[k - 1], lengths[k - 1], &dirs[k - 1], limits);
+            speeds[k] = speeds[k].min(reachable);
+        }
+        // backward pass: every speed must allow decelerating to the next waypoint
+        for k in (0..n - 1).rev() {
+            let reachable = reachable_speed(speeds[k + 1], lengths[k], &dirs[k], limits);
+            speeds[k] = speeds[k].min(reachable);
+        }
+
+        Self::with_waypoint_speeds(path, limits, &speeds)
+    }
+
+    /// Construct a trajectory which passes through each waypoint at the given speed.
+    /// Consecutive speeds must be reachable from one another under the acceleration limits.
+    fn with_waypoint_speeds(path: Vec<Vector<N, T>>, limits: &Limits<N, T>, speeds: &[T]) -> Self {
+        let mut segments = Vec::with_capacity(path.len().saturating_sub(1));
+        let mut start = T::zero();
+        for (k, w) in path.windows(2).enumerate() {
+            let length = Euclidean.distance(&w[0], &w[1]);
+            let dir = direction(&w[0], &w[1], length);
+            let segment = Segment::new(
+                start,
+                length,
+                speed_limit(&dir, &limits.velocity),
+                speed_limit(&dir, &limits.acceleration),
+                speeds[k],
+                speeds[k + 1],
+            );
+            start = start + segment.duration();
+            segments.push(segment);
+        }
+        Self {
+            waypoints: path,
+            segments,
+        }
+    }
+
+    #[must_use]
+    /// Get the waypoints of the path followed by this trajectory.
+    pub fn waypoints(&self) -> &[Vector<N, T>] {
+        &self.waypoints
+    }
+
+    #[must_use]
+    /// Get the time at which this trajectory reaches each waypoint.
+    pub fn times(&self) -> Vec<T> {
+        let mut times: Vec<T> = self.segments.iter().map(|s| s.start).collect();
+        if !self.waypoints.is_empty() {
+            times.push(self.duration());
+        }
+        times
+    }
+
+    #[must_use]
+    /// Get the total duration of this trajectory.
+    pub fn duration(&self) -> T {
+        self.segments
+            .last()
+            .map_or_else(T::zero, |s| s.start + s.duration())
+    }
+
+    /// Find the segment being traversed at time `t`, along with the time since it began.
+    fn segment_at(&self, t: T) -> Option<(usize, T)> {
+        let k = self
+            .segments
+            .partition_point(|s| s.start <= t)
+            .checked_sub(1)?;
+        Some((k, t - self.segments[k].start))
+    }
+
+    #[must_use]
+    /// Sample the configuration of the robot at time `t`.
+    /// Times outside of the trajectory are clamped to its start or end.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the trajectory has no waypoints.
+    pub fn sample(&self, t: T) -> Vector<N, T> {
+        let Some((k, dt)) = self.segment_at(t) else {
+            return self.waypoints[0];
+        };
+        let segment = &self.segments[k];
+        let (traveled, _) = segment.state(dt);
+        if traveled >= segment.length {
+            return self.waypoints[k + 1];
+        }
+        let frac = traveled / segment.length;
+        let (from, to) = (&self.waypoints[k], &self.waypoints[k + 1]);
+        Vector(core::array::from_fn(|j| from[j] + (to[j] - from[j]) * frac))
+    }
+
+    #[must_use]
+    /// Sample the velocity of each joint at time `t`.
+    /// Times outside of the trajectory are clamped to its start or end.
+    pub fn velocity(&self, t: T) -> Vector<N, T> {
+        let Some((k, dt)) = self.segment_at(t) else {
+            return Vector([T::zero(); N]);
+        };
+        let segment = &self.segments[k];
+        let (_, v) = segment.state(dt);
+        let dir = direction(&self.waypoints[k], &self.waypoints[k + 1], segment.length);
+        Vector(dir.map(|x| x * v))
+    }
+}
+
+/// The largest change in unit direction at a waypoint, roughly in radians, which
+/// [`Trajectory::time_optimal`] will take without stopping.
+const MAX_SMOOTH_TURN: f64 = 0.05;
+
+/// Compute the unit direction from `a` to `b`, given that they are `length` apart.
+fn direction<const N: usize, T: Float>(
+    a: &Vector<N, T>,
+    b: &Vector<N, T>,
+    length: T,
+) -> Vector<N, T> {
+    if length <= T::zero() {
+        return Vector([T::zero(); N]);
+    }
+    Vector(core::array::from_fn(|j| (b[j] - a[j]) / length))
+}
+
+/// Compute the fastest speed reachable from speed `v` over a segment of length `length` in
+/// direction `dir`.
+/// A segment of zero length takes no time, so its speed cannot change along it.
+fn reachable_speed<const N: usize, T: Float>(
+    v: T,
+    length: T,
+    dir: &Vector<N, T>,
+    limits: &Limits<N, T>,
+) -> T {
+    if length <= T::zero() {
+        return v;
+    }
+    let two = T::one() + T::one();
+    let a = speed_limit(dir, &limits.acceleration);
+    (v * v + two * a * length).sqrt()
+}
+
+/// Compute the maximum rate along a unit direction `dir` such that every joint is within its
+/// limit in `per_joint`.
+fn speed_limit<const N: usize, T: Float>(dir: &Vector<N, T>, per_joint: &Vector<N, T>) -> T {
+    dir.iter()
+        .zip(per_joint.iter())
+        .filter(|(d, _)| !d.is_zero())
+        .fold(T::infinity(), |lim, (&d, &l)| lim.min(l / d.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: Limits<2> = Limits {
+        velocity: Vector([1.0, 2.0]),
+        acceleration: Vector([1.0, 4.0]),
+    };
+
+    fn assert_within_limits(traj: &Trajectory<2>) {
+        let steps = 1000;
+        for i in 0..=steps {
+            let t = traj.duration() * f64::from(i) / f64::from(steps);
+            let v = traj.velocity(t);
+            assert!(v[0].abs() <= LIMITS.velocity[0] + 1e-9);
+            assert!(v[1].abs() <= LIMITS.velocity[1] + 1e-9);
+        }
+    }
+
+    /// Assert that the acceleration of every joint stays within its limit: within each segment,
+    /// across every join between segments, and through each waypoint.
+    fn assert_accel_within_limits(traj: &Trajectory<2>) {
+        let steps = 100;
+        let dirs: Vec<Vector<2>> = traj
+            .waypoints
+            .windows(2)
+            .zip(&traj.segments)
+            .map(|(w, s)| direction(&w[0], &w[1], s.length))
+            .collect();
+        for (segment, dir) in traj.segments.iter().zip(&dirs) {
+            let duration = segment.duration();
+            if duration <= 0.0 {
+                // a segment taking no time must not change speed, or its acceleration is infinite
+                assert!((segment.v_start - segment.v_end).abs() < 1e-12);
+                continue;
+            }
+            let h = duration / f64::from(steps);
+            for i in 0..steps {
+                let (_, v0) = segment.state(h * f64::from(i));
+                let (_, v1) = segment.state(h * f64::from(i + 1));
+                for j in 0..2 {
+                    let a = (v1 - v0).abs() / h * dir[j].abs();
+                    assert!(a <= LIMITS.acceleration[j] + 1e-6);
+                }
+            }
+        }
+
+        // the speed must be continuous across each join between segments
+        for pair in traj.segments.windows(2) {
+            assert!((pair[0].v_end - pair[1].v_start).abs() < 1e-12);
+        }
+
+        // the velocity must be continuous through each waypoint, up to the small jumps allowed at
+        // gentle turns
+        let eps = 1e-9;
+        let times = traj.times();
+        for &t in &times[1..times.len() - 1] {
+            let (before, after) = (traj.velocity(t - eps), traj.velocity(t + eps));
+            let speed = Euclidean.distance(&traj.velocity(t), &Vector::new([0.0; 2]));
+            let allowed_jump = speed.mul_add(MAX_SMOOTH_TURN, 1e-9);
+            for ((&v0, &v1), &limit) in before
+                .iter()
+                .zip(after.iter())
+                .zip(LIMITS.acceleration.iter())
+            {
+                assert!((v1 - v0).abs() <= limit.mul_add(2.0 * eps, allowed_jump));
+            }
+        }
+    }
+
+    #[test]
+    fn single_segment() {
+        // accelerate for 1s to reach 1 m/s over 0.5 m, cruise for 1 m, then decelerate
+        let traj = Trajectory::trapezoidal(
+            vec![Vector::new([0.0, 0.0]), Vector::new([2.0, 0.0])],
+            &LIMITS,
+        );
+        assert_accel_within_limits(&traj);
+        assert!((traj.duration() - 3.0).abs() < 1e-12);
+        assert_eq!(traj.sample(0.0), Vector::new([0.0, 0.0]));
+        assert!((traj.sample(1.0)[0] - 0.5).abs() < 1e-12);
+        assert!((traj.sample(1.5)[0] - 1.0).abs() < 1e-12);
+        assert!((traj.velocity(1.5)[0] - 1.0).abs() < 1e-12);
+        assert_eq!(traj.sample(10.0), Vector::new([2.0, 0.0]));
+        assert_eq!(traj.velocity(10.0), Vector::new([0.0, 0.0]));
+    }
+
+    #[test]
+    fn triangular_profile() {
+        // too short to reach full speed: accelerate for 0.5 s and decelerate for 0.5 s
+        let traj = Trajectory::trapezoidal(
+            vec![Vector::new([0.0, 0.0]), Vector::new([0.25, 0.0])],
+            &LIMITS,
+        );
+        assert!((traj.duration() - 1.0).abs() < 1e-12);
+        assert!((traj.velocity(0.5)[0] - 0.5).abs() < 1e-12);
+        assert_accel_within_limits(&traj);
+    }
+
+    #[test]
+    fn stops_at_waypoints() {
+        let path = vec![
+            Vector::new([0.0, 0.0]),
+            Vector::new([1.0, 0.0]),
+            Vector::new([1.0, 1.0]),
+        ];
+        let traj = Trajectory::trapezoidal(path.clone(), &LIMITS);
+        let times = traj.times();
+        assert_eq!(times.len(), 3);
+        for (t, w) in times.iter().zip(&path) {
+            assert!((traj.sample(*t)[0] - w[0]).abs() < 1e-12);
+            assert!((traj.sample(*t)[1] - w[1]).abs() < 1e-12);
+            assert!(traj.velocity(*t).iter().all(|v| v.abs() < 1e-12));
+        }
+        assert_within_limits(&traj);
+        assert_accel_within_limits(&traj);
+    }
+
+    #[test]
+    fn time_optimal_is_faster() {
+        // a gentle arc, densely sampled
+        let path: Vec<Vector<2>> = (0..=100)
+            .map(|i| {
+                let theta = f64::from(i) / 100.0;
+                Vector::new([2.0 * theta.sin(), 2.0 * (1.0 - theta.cos())])
+            })
+            .collect();
+        let slow = Trajectory::trapezoidal(path.clone(), &LIMITS);
+        let fast = Trajectory::time_optimal(path.clone(), &LIMITS);
+        assert!(fast.duration() < slow.duration());
+        assert_eq!(fast.sample(0.0), path[0]);
+        assert_eq!(fast.sample(fast.duration()), path[100]);
+        assert_within_limits(&fast);
+        assert_accel_within_limits(&fast);
+    }
+
+    #[test]
+    fn sharp_corners() {
+        // a right-angle corner and a reversal must both be taken at rest
+        for path in [
+            vec![
+                Vector::new([0.0, 0.0]),
+                Vector::new([1.0, 0.0]),
+                Vector::new([1.0, 1.0]),
+            ],
+            vec![
+                Vector::new([0.0, 0.0]),
+                Vector::new([1.0, 0.0]),
+                Vector::new([0.0, 0.0]),
+            ],
+        ] {
+            let traj = Trajectory::time_optimal(path, &LIMITS);
+            let t = traj.times()[1];
+            assert!(traj.velocity(t).iter().all(|v| v.abs() < 1e-12));
+            assert_within_limits(&traj);
+            assert_accel_within_limits(&traj);
+        }
+    }
+
+    #[test]
+    fn zero_length_segments() {
+        // repeated waypoints, both on a straight line and at a corner
+        let path = vec![
+            Vector::new([0.0, 0.0]),
+            Vector::new([0.5, 0.0]),
+            Vector::new([0.5, 0.0]),
+            Vector::new([1.0, 0.0]),
+            Vector::new([1.0, 0.5]),
+            Vector::new([1.0, 0.5]),
+        ];
+        for traj in [
+            Trajectory::trapezoidal(path.clone(), &LIMITS),
+            Trajectory::time_optimal(path.clone(), &LIMITS),
+        ] {
+            assert!(traj.duration().is_finite());
+            assert_eq!(traj.sample(traj.duration()), path[5]);
+            for (t, w) in traj.times().iter().zip(&path) {
+                assert!((traj.sample(*t)[0] - w[0]).abs() < 1e-12);
+                assert!((traj.sample(*t)[1] - w[1]).abs() < 1e-12);
+            }
+            assert_within_limits(&traj);
+            assert_accel_within_limits(&traj);
+        }
+    }
+}
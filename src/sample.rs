@@ -2,8 +2,11 @@
 
 use core::array;
 
-use crate::space::{Angle, Pose2d, Vector};
-use num_traits::{float::FloatCore, FloatConst};
+use crate::{
+    metric::{Euclidean, Metric},
+    space::{Angle, Orient, Pose2d, Pose3d, Vector},
+};
+use num_traits::{float::FloatCore, Float, FloatConst};
 use rand::{
     distributions::{uniform::SampleUniform, Bernoulli, Distribution},
     Rng,
@@ -12,26 +15,52 @@ use rand::{
 /// A sampler for a configuration.
 ///
 /// `C` is the type of the configuration, and `RNG` is a source of randomness.
+///
+/// A tuple of samplers can sample a tuple of configurations, drawing each element from the
+/// sampler at the same index.
 pub trait Sample<C, RNG> {
     /// Sample a configuration, using `rng` as a source of randomness.
     fn sample(&self, rng: &mut RNG) -> C;
 }
 
-impl<A, B, S, RNG> Sample<(A, B), RNG> for S
-where
-    S: Sample<A, RNG> + Sample<B, RNG>,
-{
-    fn sample(&self, _: &mut RNG) -> (A, B) {
-        todo!()
-    }
+/// A sampler which can restrict its samples to the configurations which could possibly improve
+/// upon an existing solution.
+///
+/// `D` is the cost of a solution.
+pub trait InformedSample<C, D, RNG>: Sample<C, RNG> {
+    /// Inform this sampler that a solution of cost `cost` has been found, so it need only sample
+    /// configurations which could be part of a cheaper solution.
+    fn set_best_cost(&mut self, cost: D);
 }
 
+/// Implement `Sample` for a tuple of samplers, sampling each element of a tuple of configurations
+/// from the sampler at the same index.
+macro_rules! sample_tuple {
+    ($($c:ident $s:ident $i:tt),*) => {
+        impl<RNG, $($c, $s: Sample<$c, RNG>),*> Sample<($($c,)*), RNG> for ($($s,)*) {
+            fn sample(&self, rng: &mut RNG) -> ($($c,)*) {
+                ($(self.$i.sample(rng),)*)
+            }
+        }
+    };
+}
+
+sample_tuple!(A SA 0);
+sample_tuple!(A SA 0, B SB 1);
+sample_tuple!(A SA 0, B SB 1, C SC 2);
+sample_tuple!(A SA 0, B SB 1, C SC 2, D SD 3);
+sample_tuple!(A SA 0, B SB 1, C SC 2, D SD 3, E SE 4);
+sample_tuple!(A SA 0, B SB 1, C SC 2, D SD 3, E SE 4, F SF 5);
+sample_tuple!(A SA 0, B SB 1, C SC 2, D SD 3, E SE 4, F SF 5, G SG 6);
+sample_tuple!(A SA 0, B SB 1, C SC 2, D SD 3, E SE 4, F SF 5, G SG 6, H SH 7);
+
 impl<RNG: Rng> Sample<bool, RNG> for Bernoulli {
     fn sample(&self, rng: &mut RNG) -> bool {
         <Self as Distribution<bool>>::sample(self, rng)
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// A rectangular prism of values of `T`.
 /// When used as [`Sample`], this rectangle inclusively samples all values.
 pub struct Rectangle<T> {
@@ -41,9 +70,194 @@ pub struct Rectangle<T> {
     pub max: T,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// A sampler for all possible values of a datum.
 pub struct Everywhere;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A ball of values of `C` around a center, with radius measured in units of `D`.
+/// When used as [`Sample`], this ball is sampled uniformly.
+///
+/// For [`Orient`]s, the radius is the maximum angle (in radians) between the center and any
+/// sampled orientation.
+pub struct Ball<C, D> {
+    /// The center of the ball.
+    pub center: C,
+    /// The radius of the ball.
+    pub radius: D,
+}
+
+#[derive(Clone, Debug)]
+/// The informed set of a problem with a single start and goal in a Euclidean space.
+///
+/// This is the prolate hyperspheroid of all points whose total Euclidean distance to the start and
+/// goal is at most the cost of the best solution, intersected with a bounding rectangle.
+/// When used as [`Sample`], this set is sampled uniformly.
+/// Until a solution's cost is provided with [`InformedSample::set_best_cost`], the whole bounding
+/// rectangle is sampled.
+///
+/// # Citation
+///
+/// ```bibtex
+/// @inproceedings{gammell2014informed,
+///   title={Informed RRT*: Optimal sampling-based path planning focused via direct sampling of an
+///          admissible ellipsoidal heuristic},
+///   author={Gammell, Jonathan D and Srinivasa, Siddhartha S and Barfoot, Timothy D},
+///   booktitle={2014 IEEE/RSJ International Conference on Intelligent Robots and Systems},
+///   pages={2997--3004},
+///   year={2014},
+///   organization={IEEE}
+/// }
+/// ```
+pub struct InformedSet<const N: usize, T = f64> {
+    bounds: Rectangle<Vector<N, T>>,
+    start: Vector<N, T>,
+    goal: Vector<N, T>,
+    /// The midpoint of `start` and `goal`.
+    center: Vector<N, T>,
+    /// The Householder vector of the reflection which takes the first axis onto the axis from
+    /// `start` to `goal`.
+    /// If zero, no reflection is needed.
+    reflect: Vector<N, T>,
+    /// The distance between `start` and `goal`.
+    min_cost: T,
+    /// The cost of the best known solution.
+    cost: T,
+}
+
+impl<const N: usize, T: Float> InformedSet<N, T> {
+    /// Construct a new informed set for paths from `start` to `goal` which are contained in
+    /// `bounds`.
+    pub fn new(bounds: Rectangle<Vector<N, T>>, start: Vector<N, T>, goal: Vector<N, T>) -> Self {
+        let two = T::one() + T::one();
+        let min_cost = Euclidean.distance(&start, &goal);
+        let center = Vector(array::from_fn(|i| (start[i] + goal[i]) / two));
+        let mut reflect = Vector([T::zero(); N]);
+        if N > 0 && min_cost > T::zero() {
+            // v = e_1 - a, where a is the unit vector from start to goal
+            for i in 0..N {
+                reflect[i] = -(goal[i] - start[i]) / min_cost;
+            }
+            reflect[0] = reflect[0] + T::one();
+        }
+        Self {
+            bounds,
+            start,
+            goal,
+            center,
+            reflect,
+            min_cost,
+            cost: T::infinity(),
+        }
+    }
+
+    /// Get the cost of the best known solution.
+    pub const fn best_cost(&self) -> T {
+        self.cost
+    }
+
+    /// Determine whether `x` is inside the bounding rectangle.
+    fn in_bounds(&self, x: &Vector<N, T>) -> bool {
+        (0..N).all(|i| self.bounds.min[i] <= x[i] && x[i] <= self.bounds.max[i])
+    }
+
+    /// Sample uniformly from the prolate hyperspheroid with transverse radius `r1` and conjugate
+    /// radius `r2`, ignoring the bounding rectangle.
+    fn sample_spheroid<RNG: Rng>(&self, r1: T, r2: T, rng: &mut RNG) -> Vector<N, T>
+    where
+        T: FloatConst + SampleUniform,
+    {
+        // sample from the unit ball by normalizing a Gaussian, then scaling the radius
+        let mut x = Vector(array::from_fn(|_| {
+            // Box-Muller transform
+            let u1 = T::one() - rng.gen_range(T::zero()..T::one());
+            let u2 = rng.gen_range(T::zero()..T::TAU());
+            (-(T::one() + T::one()) * u1.ln()).sqrt() * u2.cos()
+        }));
+        let norm = Euclidean.distance(&x, &Vector([T::zero(); N]));
+        let n = T::from(N).expect("dimension must be representable");
+        let r = rng.gen_range(T::zero()..=T::one()).powf(n.recip()) / norm;
+        for (i, xi) in x.iter_mut().enumerate() {
+            *xi = *xi * r * if i == 0 { r1 } else { r2 };
+        }
+
+        // reflect the first axis onto the start-goal axis
+        let vv = self.reflect.iter().fold(T::zero(), |a, &v| a + v * v);
+        if vv > T::zero() {
+            let vx = self
+                .reflect
+                .iter()
+                .zip(x.iter())
+                .fold(T::zero(), |a, (&v, &x)| a + v * x);
+            let scl = (vx + vx) / vv;
+            for i in 0..N {
+                x[i] = x[i] - scl * self.reflect[i];
+            }
+        }
+
+        for i in 0..N {
+            x[i] = x[i] + self.center[i];
+        }
+        x
+    }
+}
+
+impl<const N: usize, T, RNG: Rng> Sample<Vector<N, T>, RNG> for InformedSet<N, T>
+where
+    T: Float + FloatConst + FloatCore + SampleUniform,
+{
+    fn sample(&self, rng: &mut RNG) -> Vector<N, T> {
+        if !Float::is_finite(self.cost) {
+            return self.bounds.sample(rng);
+        }
+        let two = T::one() + T::one();
+        let cost = Float::max(self.cost, self.min_cost);
+        let r1 = cost / two;
+        let r2 = (cost * cost - self.min_cost * self.min_cost).sqrt() / two;
+
+        // sample whichever of the spheroid and the rectangle is smaller, and reject samples
+        // outside the other
+        let mut ball_volume = T::one();
+        let mut d = N % 2;
+        if d == 1 {
+            ball_volume = two;
+        }
+        while d < N {
+            d += 2;
+            ball_volume =
+                ball_volume * T::TAU() / T::from(d).expect("dimension must be representable");
+        }
+        let spheroid_volume = (1..N).fold(ball_volume * r1, |v, _| v * r2);
+        let rect_volume = (0..N).fold(T::one(), |v, i| {
+            v * (self.bounds.max[i] - self.bounds.min[i])
+        });
+
+        if spheroid_volume < rect_volume {
+            loop {
+                let x = self.sample_spheroid(r1, r2, rng);
+                if self.in_bounds(&x) {
+                    return x;
+                }
+            }
+        }
+        loop {
+            let x = self.bounds.sample(rng);
+            if Euclidean.distance(&x, &self.start) + Euclidean.distance(&x, &self.goal) <= cost {
+                return x;
+            }
+        }
+    }
+}
+
+impl<const N: usize, T, RNG: Rng> InformedSample<Vector<N, T>, T, RNG> for InformedSet<N, T>
+where
+    T: Float + FloatConst + FloatCore + SampleUniform,
+{
+    fn set_best_cost(&mut self, cost: T) {
+        self.cost = cost;
+    }
+}
+
 impl<const N: usize, T, RNG: Rng> Sample<Vector<N, T>, RNG> for Rectangle<Vector<N, T>>
 where
     T: FloatCore + SampleUniform,
@@ -73,3 +287,151 @@ where
         }
     }
 }
+
+impl<T, RNG: Rng> Sample<Orient<T>, RNG> for Everywhere
+where
+    T: Float + FloatConst + SampleUniform,
+{
+    /// Sample uniformly over all of _SO_(3) using Shoemake's method.
+    fn sample(&self, rng: &mut RNG) -> Orient<T> {
+        let u1 = rng.gen_range(T::zero()..=T::one());
+        let u2 = rng.gen_range(T::zero()..T::TAU());
+        let u3 = rng.gen_range(T::zero()..T::TAU());
+        let a = (T::one() - u1).sqrt();
+        let b = u1.sqrt();
+        Orient::new(a * u2.sin(), a * u2.cos(), b * u3.sin(), b * u3.cos())
+    }
+}
+
+impl<T, RNG: Rng> Sample<Pose3d<T>, RNG> for Rectangle<Vector<3, T>>
+where
+    T: Float + FloatConst + FloatCore + SampleUniform,
+{
+    fn sample(&self, rng: &mut RNG) -> Pose3d<T> {
+        Pose3d {
+            position: self.sample(rng),
+            orient: Everywhere.sample(rng),
+        }
+    }
+}
+
+impl<T, RNG: Rng> Sample<Orient<T>, RNG> for Ball<Orient<T>, T>
+where
+    T: Float + FloatConst + SampleUniform,
+{
+    fn sample(&self, rng: &mut RNG) -> Orient<T> {
+        // Under the uniform measure on SO(3), the angle `theta` of a rotation has a density
+        // proportional to `1 - cos(theta)`, so we sample it by inverting its CDF,
+        // `theta - sin(theta)`, and then pick an axis uniformly.
+        let radius = self.radius.min(T::PI());
+        let target = rng.gen_range(T::zero()..=T::one()) * (radius - radius.sin());
+        let mut theta = radius;
+        // cdf is convex, so Newton's method converges monotonically from the right
+        for _ in 0..32 {
+            let slope = T::one() - theta.cos();
+            if slope.is_zero() {
+                break;
+            }
+            let step = (theta - theta.sin() - target) / slope;
+            theta = theta - step;
+            if step <= T::epsilon() * theta {
+                break;
+            }
+        }
+
+        let z = rng.gen_range(-T::one()..=T::one());
+        let (sin_az, cos_az) = rng.gen_range(T::zero()..T::TAU()).sin_cos();
+        let r = (T::one() - z * z).sqrt();
+        let axis = Vector([r * cos_az, r * sin_az, z]);
+        self.center * Orient::from_axis_angle(axis, theta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    /// Compute the fraction of `n` orientations sampled from `sampler` which are within `angle` of
+    /// `center`.
+    fn fraction_within<S: Sample<Orient, ChaCha20Rng>>(
+        sampler: &S,
+        center: Orient,
+        angle: f64,
+        n: u32,
+    ) -> f64 {
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+        let n_within = (0..n)
+            .filter(|_| center.angle_to(sampler.sample(&mut rng)) <= angle)
+            .count();
+        f64::from(u32::try_from(n_within).unwrap()) / f64::from(n)
+    }
+
+    #[test]
+    fn orient_everywhere() {
+        // a uniformly random rotation is within pi/2 of any orientation with probability
+        // (pi/2 - 1) / pi
+        let center = Orient::from_euler(0.5, 1.0, 1.5);
+        let expected = (FRAC_PI_2 - 1.0) / PI;
+        let frac = fraction_within(&Everywhere, center, FRAC_PI_2, 10_000);
+        assert!((frac - expected).abs() < 0.02, "{frac} != {expected}");
+    }
+
+    #[test]
+    fn orient_ball() {
+        let center = Orient::from_euler(0.5, 1.0, 1.5);
+        let ball = Ball {
+            center,
+            radius: 1.0,
+        };
+        assert!((fraction_within(&ball, center, 1.0 + 1e-9, 1_000) - 1.0).abs() < f64::EPSILON);
+
+        let expected = (0.5 - 0.5f64.sin()) / (1.0 - 1.0f64.sin());
+        let frac = fraction_within(&ball, center, 0.5, 10_000);
+        assert!((frac - expected).abs() < 0.02, "{frac} != {expected}");
+    }
+
+    #[test]
+    fn informed_set() {
+        let bounds = Rectangle {
+            min: Vector::new([-1.0, -1.0, -1.0]),
+            max: Vector::new([2.0, 2.0, 0.1]),
+        };
+        let start = Vector::new([0.0, 0.0, 0.0]);
+        let goal = Vector::new([1.0, 1.0, 0.0]);
+        let mut set = InformedSet::new(bounds, start, goal);
+        let mut rng = ChaCha20Rng::seed_from_u64(2707);
+
+        // no solution yet, so sample the whole rectangle
+        let mut saw_far = false;
+        for _ in 0..1_000 {
+            let x: Vector<3> = set.sample(&mut rng);
+            assert!(set.in_bounds(&x));
+            saw_far |= Euclidean.distance(&x, &start) + Euclidean.distance(&x, &goal) > 2.0;
+        }
+        assert!(saw_far);
+
+        for cost in [1.5, 3.0, 10.0] {
+            InformedSample::<_, _, ChaCha20Rng>::set_best_cost(&mut set, cost);
+            let mut mean = Vector::new([0.0; 3]);
+            for _ in 0..1_000 {
+                let x: Vector<3> = set.sample(&mut rng);
+                assert!(set.in_bounds(&x));
+                let c = Euclidean.distance(&x, &start) + Euclidean.distance(&x, &goal);
+                assert!(c <= cost + 1e-9, "{c} > {cost}");
+                for i in 0..3 {
+                    mean[i] += x[i] / 1_000.0;
+                }
+            }
+            if cost < 3.0 {
+                // spheroid is symmetric about the center in the first two axes
+                assert!((mean[0] - 0.5).abs() < 0.05, "{mean:?}");
+                assert!((mean[1] - 0.5).abs() < 0.05, "{mean:?}");
+            }
+        }
+    }
+}
@@ -1,8 +1,12 @@
 use num_traits::{float::FloatCore, FloatConst, Zero};
 
-use crate::{nn::KdKey, space::Interpolate};
+use crate::{
+    nn::KdKey,
+    space::{Interpolate, Lerp},
+};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(transparent)]
 /// An angle (an element of [0, 2π).).
 ///
@@ -63,6 +67,24 @@ impl<T> Angle<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Angle<T>
+where
+    T: serde::Deserialize<'de> + FloatConst + FloatCore,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = T::deserialize(deserializer)?;
+        if T::zero() <= value && value < T::TAU() {
+            Ok(Self(value))
+        } else {
+            Err(serde::de::Error::custom("angle must be between 0 and 2pi"))
+        }
+    }
+}
+
 impl<T: PartialEq> Eq for Angle<T> {}
 
 impl<T: FloatCore> PartialOrd for Angle<T> {
@@ -120,6 +142,25 @@ where
     }
 }
 
+impl<T> Lerp for Angle<T>
+where
+    T: FloatCore + FloatConst,
+{
+    type Scalar = T;
+
+    /// Blend along the shorter arc from `self` to `end`.
+    fn lerp(&self, &end: &Self, t: Self::Scalar) -> Self {
+        let value = self.0 + self.signed_distance(end) * t;
+        Self(if value < T::zero() {
+            value + T::TAU()
+        } else if value >= T::TAU() {
+            value - T::TAU()
+        } else {
+            value
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::f32::consts::TAU;
@@ -4,6 +4,7 @@ mod angle;
 mod orient;
 mod pose2d;
 mod pose3d;
+mod product;
 mod vector;
 
 pub use angle::Angle;
@@ -76,6 +77,46 @@ where
     }
 }
 
+impl<T, MP, MA> Metric<Pose3d<T>> for WeightedPoseDistance<T, MP, MA>
+where
+    MP: Metric<Vector<3, T>, Distance = T>,
+    MA: Metric<Orient<T>, Distance = T>,
+    T: FloatCore,
+{
+    type Distance = T;
+    fn distance(&self, c1: &Pose3d<T>, c2: &Pose3d<T>) -> Self::Distance {
+        let pd = self.position_metric.distance(&c1.position, &c2.position);
+        let ad = self.angle_metric.distance(&c1.orient, &c2.orient);
+
+        self.position_weight * pd + self.angle_weight * ad
+    }
+}
+
+impl<T, MP, MA> DistanceAabb<Pose3d<T>> for WeightedPoseDistance<T, MP, MA>
+where
+    MP: DistanceAabb<Vector<3, T>, Distance = T>,
+    MA: DistanceAabb<Orient<T>, Distance = T>,
+    T: FloatCore,
+{
+    fn distance_to_aabb(
+        &self,
+        c: &Pose3d<T>,
+        aabb_lo: &Pose3d<T>,
+        aabb_hi: &Pose3d<T>,
+    ) -> Self::Distance {
+        self.position_weight
+            * self.position_metric.distance_to_aabb(
+                &c.position,
+                &aabb_lo.position,
+                &aabb_hi.position,
+            )
+            + self.angle_weight
+                * self
+                    .angle_metric
+                    .distance_to_aabb(&c.orient, &aabb_lo.orient, &aabb_hi.orient)
+    }
+}
+
 /// The trait for linear interpolation between configurations.
 pub trait Interpolate: Sized {
     /// The radius to which interpolation may be limited.
@@ -89,3 +130,15 @@ pub trait Interpolate: Sized {
     /// toward `end`.
     fn interpolate(&self, end: &Self, radius: Self::Distance) -> Result<Self, Self>;
 }
+
+/// The trait for blending between configurations by a continuous parameter, such as for
+/// evaluating splines.
+pub trait Lerp {
+    /// The blending parameter.
+    type Scalar;
+
+    #[must_use]
+    /// Blend from `self` to `end` by `t`, which is typically between 0 and 1.
+    /// Returns a configuration equal to `self` when `t` is 0, and equal to `end` when `t` is 1.
+    fn lerp(&self, end: &Self, t: Self::Scalar) -> Self;
+}
@@ -1,8 +1,530 @@
+use core::ops::Mul;
+
+use num_traits::{Float, FloatConst};
+
+use crate::{nn::KdKey, sample::Sample, space::Interpolate};
+
+use super::Vector;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(C)]
-/// An orientation in 3D.
-pub struct Orient<T> {
+/// An orientation in 3D, represented as a unit quaternion `xi + yj + zk + w`.
+///
+/// Orients model the special orthogonal group in 3 dimensions (_SO_(3)), also known as the set of
+/// 3D rotations.
+/// Since `q` and `-q` represent the same rotation, every `Orient` is stored in a canonical form
+/// with `w >= 0`, and all distances and interpolations respect this double cover.
+pub struct Orient<T = f64> {
     x: T,
     y: T,
     z: T,
     w: T,
 }
+
+impl<T> Orient<T> {
+    /// Construct a new orientation from the components of a quaternion `xi + yj + zk + w`.
+    /// The quaternion will be normalized, so it need not have unit length.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the quaternion has zero length or any component is not finite.
+    pub fn new(x: T, y: T, z: T, w: T) -> Self
+    where
+        T: Float,
+    {
+        let norm = (x * x + y * y + z * z + w * w).sqrt();
+        assert!(
+            norm.is_normal(),
+            "quaternion must have finite and nonzero length"
+        );
+        Self::canonical(x / norm, y / norm, z / norm, w / norm)
+    }
+
+    /// # Safety
+    ///
+    /// Will be unsafe if `(x, y, z, w)` does not have unit length or if `w < 0`.
+    pub const unsafe fn new_unchecked(x: T, y: T, z: T, w: T) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Construct the identity rotation.
+    #[must_use]
+    pub fn identity() -> Self
+    where
+        T: Float,
+    {
+        Self {
+            x: T::zero(),
+            y: T::zero(),
+            z: T::zero(),
+            w: T::one(),
+        }
+    }
+
+    /// Construct a rotation of `angle` radians about `axis` (following the right-hand rule).
+    /// `axis` need not be normalized.
+    ///
+    /// If `axis` has zero length, the result is the identity rotation.
+    pub fn from_axis_angle(axis: Vector<3, T>, angle: T) -> Self
+    where
+        T: Float,
+    {
+        let Vector([ax, ay, az]) = axis;
+        let norm = (ax * ax + ay * ay + az * az).sqrt();
+        if norm.is_zero() {
+            return Self::identity();
+        }
+        let (sin, cos) = (angle / (T::one() + T::one())).sin_cos();
+        let scl = sin / norm;
+        Self::canonical(ax * scl, ay * scl, az * scl, cos)
+    }
+
+    /// Construct a rotation from roll-pitch-yaw Euler angles.
+    ///
+    /// The resulting rotation first rolls about the X axis, then pitches about the Y axis, then
+    /// yaws about the Z axis, all in a fixed frame (i.e. `R = Rz(yaw) * Ry(pitch) * Rx(roll)`).
+    pub fn from_euler(roll: T, pitch: T, yaw: T) -> Self
+    where
+        T: Float,
+    {
+        let two = T::one() + T::one();
+        let (sr, cr) = (roll / two).sin_cos();
+        let (sp, cp) = (pitch / two).sin_cos();
+        let (sy, cy) = (yaw / two).sin_cos();
+        Self::canonical(
+            sr * cp * cy - cr * sp * sy,
+            cr * sp * cy + sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+            cr * cp * cy + sr * sp * sy,
+        )
+    }
+
+    /// Construct a rotation from a row-major rotation matrix.
+    ///
+    /// The matrix is assumed to be orthonormal with determinant 1; if it is only approximately
+    /// so, the result is normalized to the nearest valid orientation.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `m` contains non-finite values.
+    pub fn from_rotation_matrix(m: [[T; 3]; 3]) -> Self
+    where
+        T: Float,
+    {
+        // Shepperd's method: pivot on the largest diagonal term for numerical stability
+        let one = T::one();
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        if trace > m[0][0] && trace > m[1][1] && trace > m[2][2] {
+            let s = (one + trace).sqrt();
+            let d = (s + s).recip();
+            Self::new(
+                (m[2][1] - m[1][2]) * d,
+                (m[0][2] - m[2][0]) * d,
+                (m[1][0] - m[0][1]) * d,
+                s * s * d,
+            )
+        } else if m[0][0] >= m[1][1] && m[0][0] >= m[2][2] {
+            let s = (one + m[0][0] - m[1][1] - m[2][2]).sqrt();
+            let d = (s + s).recip();
+            Self::new(
+                s * s * d,
+                (m[0][1] + m[1][0]) * d,
+                (m[0][2] + m[2][0]) * d,
+                (m[2][1] - m[1][2]) * d,
+            )
+        } else if m[1][1] >= m[2][2] {
+            let s = (one + m[1][1] - m[0][0] - m[2][2]).sqrt();
+            let d = (s + s).recip();
+            Self::new(
+                (m[0][1] + m[1][0]) * d,
+                s * s * d,
+                (m[1][2] + m[2][1]) * d,
+                (m[0][2] - m[2][0]) * d,
+            )
+        } else {
+            let s = (one + m[2][2] - m[0][0] - m[1][1]).sqrt();
+            let d = (s + s).recip();
+            Self::new(
+                (m[0][2] + m[2][0]) * d,
+                (m[1][2] + m[2][1]) * d,
+                s * s * d,
+                (m[1][0] - m[0][1]) * d,
+            )
+        }
+    }
+
+    /// Get the components of this orientation's quaternion, in the order `[x, y, z, w]`.
+    pub const fn get(self) -> [T; 4]
+    where
+        T: Copy,
+    {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// Compute the row-major rotation matrix equivalent to this orientation.
+    pub fn to_rotation_matrix(self) -> [[T; 3]; 3]
+    where
+        T: Float,
+    {
+        let Self { x, y, z, w } = self;
+        let one = T::one();
+        let two = one + one;
+        [
+            [
+                one - two * (y * y + z * z),
+                two * (x * y - z * w),
+                two * (x * z + y * w),
+            ],
+            [
+                two * (x * y + z * w),
+                one - two * (x * x + z * z),
+                two * (y * z - x * w),
+            ],
+            [
+                two * (x * z - y * w),
+                two * (y * z + x * w),
+                one - two * (x * x + y * y),
+            ],
+        ]
+    }
+
+    /// Compute the roll, pitch, and yaw of this orientation, in the same convention as
+    /// [`Orient::from_euler`].
+    ///
+    /// Pitch is always in the range [-π/2, π/2].
+    pub fn to_euler(self) -> (T, T, T)
+    where
+        T: Float + FloatConst,
+    {
+        let Self { x, y, z, w } = self;
+        let one = T::one();
+        let two = one + one;
+        let roll = (two * (w * x + y * z)).atan2(one - two * (x * x + y * y));
+        let sin_pitch = two * (w * y - z * x);
+        let pitch = if sin_pitch.abs() >= one {
+            T::FRAC_PI_2().copysign(sin_pitch)
+        } else {
+            sin_pitch.asin()
+        };
+        let yaw = (two * (w * z + x * y)).atan2(one - two * (y * y + z * z));
+        (roll, pitch, yaw)
+    }
+
+    /// Compute the axis and angle of this rotation.
+    /// The angle is always in the range [0, π].
+    ///
+    /// If this is the identity rotation, the axis is arbitrarily chosen to be the X axis.
+    pub fn to_axis_angle(self) -> (Vector<3, T>, T)
+    where
+        T: Float,
+    {
+        let Self { x, y, z, w } = self;
+        let sin_half = (x * x + y * y + z * z).sqrt();
+        if sin_half.is_zero() {
+            return (Vector([T::one(), T::zero(), T::zero()]), T::zero());
+        }
+        let angle = (sin_half.atan2(w)) * (T::one() + T::one());
+        (Vector([x / sin_half, y / sin_half, z / sin_half]), angle)
+    }
+
+    /// Compute the inverse of this rotation.
+    #[must_use]
+    pub fn inverse(self) -> Self
+    where
+        T: Float,
+    {
+        Self::canonical(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Rotate a vector `v` by this orientation.
+    pub fn rotate(self, v: Vector<3, T>) -> Vector<3, T>
+    where
+        T: Float,
+    {
+        // v' = v + 2w(u x v) + 2u x (u x v), where u is the vector part of the quaternion
+        let Vector([vx, vy, vz]) = v;
+        let Self { x, y, z, w } = self;
+        let two = T::one() + T::one();
+        let tx = two * (y * vz - z * vy);
+        let ty = two * (z * vx - x * vz);
+        let tz = two * (x * vy - y * vx);
+        Vector([
+            vx + w * tx + (y * tz - z * ty),
+            vy + w * ty + (z * tx - x * tz),
+            vz + w * tz + (x * ty - y * tx),
+        ])
+    }
+
+    /// Compute the angle (in radians) of the smallest rotation which takes `self` to `other`.
+    ///
+    /// This is the geodesic distance between `self` and `other` in _SO_(3), and is always in the
+    /// range [0, π].
+    pub fn angle_to(self, other: Self) -> T
+    where
+        T: Float,
+    {
+        let Self { x, y, z, w } = self.inverse() * other;
+        (x * x + y * y + z * z).sqrt().atan2(w) * (T::one() + T::one())
+    }
+
+    /// Spherically interpolate between `self` and `other` along the shortest path in _SO_(3).
+    ///
+    /// `t = 0` yields `self`, and `t = 1` yields `other`.
+    #[must_use]
+    pub fn slerp(self, other: Self, t: T) -> Self
+    where
+        T: Float,
+    {
+        let Self { x, y, z, w } = self.inverse() * other;
+        let sin_half = (x * x + y * y + z * z).sqrt();
+        if sin_half.is_zero() {
+            return self;
+        }
+        let (sin, cos) = (sin_half.atan2(w) * t).sin_cos();
+        let scl = sin / sin_half;
+        let step = Self::canonical(x * scl, y * scl, z * scl, cos);
+        self * step
+    }
+
+    /// Construct an orientation from unit-length quaternion components, flipping its sign if
+    /// needed to make `w` nonnegative.
+    fn canonical(x: T, y: T, z: T, w: T) -> Self
+    where
+        T: Float,
+    {
+        if w < T::zero() {
+            Self {
+                x: -x,
+                y: -y,
+                z: -z,
+                w: -w,
+            }
+        } else {
+            Self { x, y, z, w }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+/// Deserialized orientations are normalized, as in [`Orient::new`].
+impl<'de, T> serde::Deserialize<'de> for Orient<T>
+where
+    T: serde::Deserialize<'de> + Float,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Orient")]
+        struct Quaternion<T> {
+            x: T,
+            y: T,
+            z: T,
+            w: T,
+        }
+
+        let Quaternion { x, y, z, w } = Quaternion::<T>::deserialize(deserializer)?;
+        if (x * x + y * y + z * z + w * w).sqrt().is_normal() {
+            Ok(Self::new(x, y, z, w))
+        } else {
+            Err(serde::de::Error::custom(
+                "quaternion must have finite and nonzero length",
+            ))
+        }
+    }
+}
+
+impl<T: Float> Default for Orient<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: Float> Mul for Orient<T> {
+    type Output = Self;
+
+    /// Compose two rotations, such that `(a * b).rotate(v) == a.rotate(b.rotate(v))`.
+    fn mul(self, rhs: Self) -> Self::Output {
+        let Self {
+            x: x0,
+            y: y0,
+            z: z0,
+            w: w0,
+        } = self;
+        let Self {
+            x: x1,
+            y: y1,
+            z: z1,
+            w: w1,
+        } = rhs;
+        Self::canonical(
+            w0 * x1 + x0 * w1 + y0 * z1 - z0 * y1,
+            w0 * y1 - x0 * z1 + y0 * w1 + z0 * x1,
+            w0 * z1 + x0 * y1 - y0 * x1 + z0 * w1,
+            w0 * w1 - x0 * x1 - y0 * y1 - z0 * z1,
+        )
+    }
+}
+
+impl<T> KdKey for Orient<T>
+where
+    T: Float,
+{
+    fn dimension() -> usize {
+        4
+    }
+
+    fn compare(&self, rhs: &Self, k: usize) -> core::cmp::Ordering {
+        let (a, b) = match k {
+            0 => (self.x, rhs.x),
+            1 => (self.y, rhs.y),
+            2 => (self.z, rhs.z),
+            3 => (self.w, rhs.w),
+            _ => panic!("cannot test dimension greater than 3"),
+        };
+        a.partial_cmp(&b).unwrap()
+    }
+
+    fn assign(&mut self, src: &Self, k: usize) {
+        match k {
+            0 => self.x = src.x,
+            1 => self.y = src.y,
+            2 => self.z = src.z,
+            3 => self.w = src.w,
+            _ => panic!("cannot assign dimension greater than 3"),
+        }
+    }
+
+    fn lower_bound() -> Self {
+        Self {
+            x: -T::one(),
+            y: -T::one(),
+            z: -T::one(),
+            w: -T::one(),
+        }
+    }
+
+    fn upper_bound() -> Self {
+        Self {
+            x: T::one(),
+            y: T::one(),
+            z: T::one(),
+            w: T::one(),
+        }
+    }
+}
+
+impl<T> Interpolate for Orient<T>
+where
+    T: Float,
+{
+    type Distance = T;
+
+    fn interpolate(&self, end: &Self, radius: Self::Distance) -> Result<Self, Self> {
+        let dist = self.angle_to(*end);
+        if dist <= radius {
+            Err(*end)
+        } else {
+            Ok(self.slerp(*end, radius / dist))
+        }
+    }
+}
+
+impl<T, RNG> Sample<Self, RNG> for Orient<T>
+where
+    T: Clone,
+{
+    fn sample(&self, _: &mut RNG) -> Self {
+        self.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+    use crate::metric::{Metric, SquaredEuclidean};
+
+    fn assert_close(a: Orient, b: Orient) {
+        assert!(a.angle_to(b) <= 1e-9, "{a:?} and {b:?} must be equal");
+    }
+
+    #[test]
+    fn axis_angle_roundtrip() {
+        let q = Orient::from_axis_angle(Vector([1.0, 2.0, -0.5]), 2.0);
+        let (axis, angle) = q.to_axis_angle();
+        assert!((angle - 2.0).abs() <= 1e-9);
+        assert_close(Orient::from_axis_angle(axis, angle), q);
+    }
+
+    #[test]
+    fn matrix_roundtrip() {
+        for q in [
+            Orient::from_euler(0.1, 0.2, 0.3),
+            Orient::from_euler(PI, 0.0, 0.0),
+            Orient::from_euler(0.0, PI, 0.0),
+            Orient::from_euler(0.0, 0.0, PI),
+            Orient::from_euler(3.0, -1.0, 2.5),
+        ] {
+            assert_close(Orient::from_rotation_matrix(q.to_rotation_matrix()), q);
+        }
+    }
+
+    #[test]
+    fn euler_roundtrip() {
+        let (r, p, y) = Orient::from_euler(0.3, -0.7, 2.0).to_euler();
+        assert!((r - 0.3).abs() <= 1e-9);
+        assert!((p + 0.7).abs() <= 1e-9);
+        assert!((y - 2.0).abs() <= 1e-9);
+    }
+
+    #[test]
+    fn compose_rotate() {
+        let yaw = Orient::from_axis_angle(Vector([0.0, 0.0, 1.0]), FRAC_PI_2);
+        let roll = Orient::from_axis_angle(Vector([1.0, 0.0, 0.0]), FRAC_PI_2);
+
+        // roll takes y to z, and yaw leaves z alone
+        let rotated = (yaw * roll).rotate(Vector([0.0, 1.0, 0.0]));
+        assert!(SquaredEuclidean.distance(&rotated, &Vector([0.0, 0.0, 1.0])) <= 1e-9);
+
+        assert_close(yaw * yaw.inverse(), Orient::identity());
+    }
+
+    #[test]
+    fn double_cover() {
+        let a = Orient::from_axis_angle(Vector([0.0, 0.0, 1.0]), 0.9 * PI);
+        let b = Orient::from_axis_angle(Vector([0.0, 0.0, 1.0]), -0.9 * PI);
+
+        // the short way around is through pi
+        assert!((a.angle_to(b) / PI - 0.2).abs() <= 1e-9);
+        assert_close(
+            a.slerp(b, 0.5),
+            Orient::from_axis_angle(Vector([0.0, 0.0, 1.0]), PI),
+        );
+    }
+
+    #[test]
+    fn interpolate_steps() {
+        let a = Orient::identity();
+        let b = Orient::from_axis_angle(Vector([0.0, 1.0, 0.0]), 1.0);
+        let c = a.interpolate(&b, 0.25).unwrap();
+        assert!((a.angle_to(c) - 0.25).abs() <= 1e-9);
+        assert!((c.angle_to(b) - 0.75).abs() <= 1e-9);
+        assert_eq!(c.interpolate(&b, 1.0), Err(b));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize() {
+        let q: Orient = serde_json::from_str(r#"{"x":0.0,"y":0.0,"z":0.0,"w":-2.0}"#).unwrap();
+        assert_eq!(q, Orient::identity());
+        assert!(serde_json::from_str::<Orient>(r#"{"x":0.0,"y":0.0,"z":0.0,"w":0.0}"#).is_err());
+
+        let r = Orient::from_euler(0.1, 0.2, 0.3);
+        let s = serde_json::to_string(&r).unwrap();
+        assert_close(serde_json::from_str(&s).unwrap(), r);
+    }
+}
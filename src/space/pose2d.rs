@@ -2,11 +2,22 @@ use core::iter::Sum;
 
 use num_traits::{float::FloatCore, FloatConst};
 
-use crate::{nn::KdKey, sample::Sample, space::Interpolate};
+use crate::{
+    nn::KdKey,
+    sample::Sample,
+    space::{Interpolate, Lerp},
+};
 
 use super::{Angle, PoseRadius, Vector};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        deserialize = "Vector<2, T>: serde::Deserialize<'de>, Angle<T>: serde::Deserialize<'de>"
+    ))
+)]
 /// A pose in 2 dimensions.
 pub struct Pose2d<T = f64> {
     /// The translation vector.
@@ -33,6 +44,20 @@ where
     }
 }
 
+impl<T> Lerp for Pose2d<T>
+where
+    T: FloatCore + FloatConst,
+{
+    type Scalar = T;
+
+    fn lerp(&self, end: &Self, t: Self::Scalar) -> Self {
+        Self {
+            position: self.position.lerp(&end.position, t),
+            angle: self.angle.lerp(&end.angle, t),
+        }
+    }
+}
+
 impl<T, RNG> Sample<Self, RNG> for Pose2d<T>
 where
     T: Clone,
@@ -57,7 +82,7 @@ where
             k if k < 2 => self.position.assign(&src.position, k),
             2 => self.angle.assign(&src.angle, 0),
             _ => panic!("cannot assign dimension greater than 2"),
-        };
+        }
     }
 
     fn compare(&self, rhs: &Self, k: usize) -> core::cmp::Ordering {
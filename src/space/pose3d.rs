@@ -1,9 +1,118 @@
-use super::{Orient, Vector};
+use num_traits::{float::FloatCore, Float};
 
-/// A position in 3 dimensions.
-pub struct Pose3d<T> {
+use crate::{nn::KdKey, sample::Sample, space::Interpolate};
+
+use super::{Orient, PoseRadius, Vector};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        deserialize = "Vector<3, T>: serde::Deserialize<'de>, Orient<T>: serde::Deserialize<'de>"
+    ))
+)]
+/// A pose in 3 dimensions.
+pub struct Pose3d<T = f64> {
     /// The translation vector.
     pub position: Vector<3, T>,
     /// The orientation.
     pub orient: Orient<T>,
 }
+
+impl<T> Interpolate for Pose3d<T>
+where
+    T: FloatCore + Float,
+{
+    type Distance = PoseRadius<T>;
+    fn interpolate(&self, end: &Self, radius: Self::Distance) -> Result<Self, Self> {
+        let pos_res = self
+            .position
+            .interpolate(&end.position, radius.position_dist);
+        let orient_res = self.orient.interpolate(&end.orient, radius.angle_dist);
+
+        match (pos_res, orient_res) {
+            (Err(position), Err(orient)) => Err(Self { position, orient }),
+            (Ok(position) | Err(position), Ok(orient) | Err(orient)) => {
+                Ok(Self { position, orient })
+            }
+        }
+    }
+}
+
+impl<T, RNG> Sample<Self, RNG> for Pose3d<T>
+where
+    T: Clone,
+{
+    fn sample(&self, _: &mut RNG) -> Self {
+        self.clone()
+    }
+}
+
+impl<T> KdKey for Pose3d<T>
+where
+    Vector<3, T>: KdKey,
+    Orient<T>: KdKey,
+    Self: Clone,
+{
+    fn dimension() -> usize {
+        7
+    }
+
+    fn assign(&mut self, src: &Self, k: usize) {
+        match k {
+            k if k < 3 => self.position.assign(&src.position, k),
+            k if k < 7 => self.orient.assign(&src.orient, k - 3),
+            _ => panic!("cannot assign dimension greater than 6"),
+        }
+    }
+
+    fn compare(&self, rhs: &Self, k: usize) -> core::cmp::Ordering {
+        match k {
+            k if k < 3 => self.position.compare(&rhs.position, k),
+            k if k < 7 => self.orient.compare(&rhs.orient, k - 3),
+            _ => panic!("cannot test dimension greater than 6"),
+        }
+    }
+
+    fn lower_bound() -> Self {
+        Self {
+            position: Vector::lower_bound(),
+            orient: Orient::lower_bound(),
+        }
+    }
+
+    fn upper_bound() -> Self {
+        Self {
+            position: Vector::upper_bound(),
+            orient: Orient::upper_bound(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_both() {
+        let start = Pose3d {
+            position: Vector([0.0; 3]),
+            orient: Orient::identity(),
+        };
+        let end = Pose3d {
+            position: Vector([1.0, 0.0, 0.0]),
+            orient: Orient::from_axis_angle(Vector([0.0, 0.0, 1.0]), 0.5),
+        };
+        let radius = PoseRadius {
+            angle_dist: 0.1,
+            position_dist: 0.1,
+        };
+        let mut c = start;
+        while let Ok(next) = c.interpolate(&end, radius) {
+            assert!(c.orient.angle_to(next.orient) <= 0.1 + 1e-9);
+            c = next;
+        }
+        assert_eq!(c.interpolate(&end, radius), Err(end));
+    }
+}
@@ -0,0 +1,115 @@
+//! Compound configuration spaces, composed as tuples of simpler spaces.
+
+use crate::{nn::KdKey, space::Interpolate};
+
+/// Implement `Interpolate` and `KdKey` for a tuple of configurations.
+///
+/// Interpolation steps each element toward its end independently, using the radius at the same
+/// index, and is only complete once every element has reached its end.
+/// The axes of a tuple for a _k_-d tree are the axes of each of its elements, in order.
+macro_rules! product_tuple {
+    ($($t:ident $i:tt),*) => {
+        impl<$($t: Interpolate),*> Interpolate for ($($t,)*) {
+            type Distance = ($($t::Distance,)*);
+
+            fn interpolate(&self, end: &Self, radius: Self::Distance) -> Result<Self, Self> {
+                let mut reached = true;
+                let res = ($(
+                    match self.$i.interpolate(&end.$i, radius.$i) {
+                        Ok(c) => {
+                            reached = false;
+                            c
+                        }
+                        Err(c) => c,
+                    },
+                )*);
+                if reached {
+                    Err(res)
+                } else {
+                    Ok(res)
+                }
+            }
+        }
+
+        impl<$($t: KdKey),*> KdKey for ($($t,)*) {
+            fn dimension() -> usize {
+                0 $(+ $t::dimension())*
+            }
+
+            fn compare(&self, rhs: &Self, k: usize) -> core::cmp::Ordering {
+                let mut offset = 0;
+                $(
+                    if k < offset + $t::dimension() {
+                        return self.$i.compare(&rhs.$i, k - offset);
+                    }
+                    offset += $t::dimension();
+                )*
+                panic!("cannot test dimension greater than {}", offset - 1)
+            }
+
+            fn assign(&mut self, src: &Self, k: usize) {
+                let mut offset = 0;
+                $(
+                    if k < offset + $t::dimension() {
+                        return self.$i.assign(&src.$i, k - offset);
+                    }
+                    offset += $t::dimension();
+                )*
+                panic!("cannot assign dimension greater than {}", offset - 1)
+            }
+
+            fn lower_bound() -> Self {
+                ($($t::lower_bound(),)*)
+            }
+
+            fn upper_bound() -> Self {
+                ($($t::upper_bound(),)*)
+            }
+        }
+    };
+}
+
+product_tuple!(A 0);
+product_tuple!(A 0, B 1);
+product_tuple!(A 0, B 1, C 2);
+product_tuple!(A 0, B 1, C 2, D 3);
+product_tuple!(A 0, B 1, C 2, D 3, E 4);
+product_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+product_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+product_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::space::{Angle, Vector};
+
+    #[test]
+    fn interpolate_tuple() {
+        let start = (Vector::new([0.0f64]), Angle::new(0.0));
+        let end = (Vector::new([1.0]), Angle::new(0.25));
+        let (v, a) = start.interpolate(&end, (0.05, 0.5)).unwrap();
+        assert!((v[0] - 0.05).abs() <= 1e-9);
+        assert_eq!(a, end.1);
+
+        assert_eq!(start.interpolate(&end, (2.0, 0.5)), Err(end));
+    }
+
+    #[test]
+    fn kd_axes() {
+        type C = (Vector<2>, Angle, Vector<3>);
+        assert_eq!(C::dimension(), 6);
+
+        let mut c = C::lower_bound();
+        let src = (
+            Vector::new([1.0, 2.0]),
+            Angle::new(3.0),
+            Vector::new([4.0, 5.0, 6.0]),
+        );
+        for k in 0..6 {
+            assert!(c.compare(&src, k).is_lt());
+            c.assign(&src, k);
+            assert!(c.compare(&src, k).is_eq());
+        }
+        assert_eq!(c, src);
+    }
+}
@@ -5,7 +5,7 @@ use core::{
 };
 use num_traits::float::FloatCore;
 
-use super::Interpolate;
+use super::{Interpolate, Lerp};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(transparent)]
@@ -44,6 +44,70 @@ impl<T, const N: usize> From<[T; N]> for Vector<N, T> {
     }
 }
 
+#[cfg(feature = "serde")]
+/// Vectors are serialized as tuples of their elements, since `serde` can only derive
+/// implementations for arrays of fixed sizes.
+impl<const N: usize, T> serde::Serialize for Vector<N, T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeTuple;
+        let mut tup = serializer.serialize_tuple(N)?;
+        for x in &self.0 {
+            tup.serialize_element(x)?;
+        }
+        tup.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize, T> serde::Deserialize<'de> for Vector<N, T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use alloc::vec::Vec;
+        use core::marker::PhantomData;
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct VectorVisitor<const N: usize, T>(PhantomData<T>);
+
+        impl<'de, const N: usize, T> Visitor<'de> for VectorVisitor<N, T>
+        where
+            T: serde::Deserialize<'de>,
+        {
+            type Value = Vector<N, T>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(formatter, "a tuple of {N} elements")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut elts = Vec::with_capacity(N);
+                while let Some(x) = seq.next_element()? {
+                    elts.push(x);
+                }
+                let len = elts.len();
+                elts.try_into()
+                    .map(Vector)
+                    .map_err(|_| A::Error::invalid_length(len, &self))
+            }
+        }
+
+        deserializer.deserialize_tuple(N, VectorVisitor(PhantomData))
+    }
+}
+
 impl<const N: usize, T> Interpolate for Vector<N, T>
 where
     T: FloatCore,
@@ -62,6 +126,17 @@ where
     }
 }
 
+impl<const N: usize, T> Lerp for Vector<N, T>
+where
+    T: FloatCore,
+{
+    type Scalar = T;
+
+    fn lerp(&self, end: &Self, t: Self::Scalar) -> Self {
+        Self(array::from_fn(|i| self[i] + (end[i] - self[i]) * t))
+    }
+}
+
 impl<const N: usize, T, RNG> Sample<Self, RNG> for Vector<N, T>
 where
     T: Clone,
@@ -74,7 +149,7 @@ where
 impl<const N: usize, T: Sub<Output = T> + FloatCore> Sub for Vector<N, T> {
     type Output = Self;
     fn sub(mut self, rhs: Self) -> Self::Output {
-        for (a, b) in self.iter_mut().zip(rhs.into_iter()) {
+        for (a, b) in self.iter_mut().zip(rhs.0) {
             *a = *a - b;
         }
         self